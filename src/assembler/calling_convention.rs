@@ -0,0 +1,57 @@
+use crate::tokenizer::declaration::DataType;
+
+use super::data::{FLOAT_ARGUMENT_REGISTERS, INTEGER_ARGUMENT_REGISTERS};
+
+
+/// Where an argument is passed to a subroutine, following the System V AMD64 calling convention
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgumentLocation {
+    /// One of `INTEGER_ARGUMENT_REGISTERS`
+    IntegerRegister(&'static str),
+    /// One of `FLOAT_ARGUMENT_REGISTERS`
+    FloatRegister(&'static str),
+    /// The nth argument passed on the stack, where the first is the closest to the return address
+    Stack(usize),
+}
+
+/// Finds where each argument of a subroutine taking `argument_types` is passed
+///
+/// `int` and `tof` arguments take the next free integer register and `flt` arguments take the
+/// next free `xmm` register. Once a kind of register runs out, the arguments of that kind are
+/// passed on the stack, in the order they were given
+///
+/// # Examples
+///
+/// ```rust
+/// // [IntegerRegister("rdi"), FloatRegister("xmm0"), IntegerRegister("rsi")]
+/// let locations = locate_arguments(&[DataType::INTEGER, DataType::FLOAT, DataType::BOOL]);
+/// ```
+pub fn locate_arguments(argument_types: &[DataType]) -> Vec<ArgumentLocation> {
+    let mut result: Vec<ArgumentLocation> = Vec::new();
+
+    let mut integer_registers = INTEGER_ARGUMENT_REGISTERS.iter();
+    let mut float_registers   = FLOAT_ARGUMENT_REGISTERS.iter();
+    let mut stack_arguments: usize = 0;
+
+    for argument_type in argument_types.iter() {
+        let register = match argument_type {
+            DataType::FLOAT => { float_registers.next().map(|x| ArgumentLocation::FloatRegister(x))     }
+            _               => { integer_registers.next().map(|x| ArgumentLocation::IntegerRegister(x)) }
+        };
+
+        match register {
+            Some(register) => { result.push(register) }
+            None => {
+                result.push(ArgumentLocation::Stack(stack_arguments));
+                stack_arguments += 1;
+            }
+        }
+    }
+
+    return result
+}
+
+/// How many of `locations` are passed on the stack
+pub fn count_stack_arguments(locations: &[ArgumentLocation]) -> usize {
+    return locations.iter().filter(|x| matches!(x, ArgumentLocation::Stack(_))).count()
+}
@@ -1,96 +1,109 @@
-pub const FUNCTION_ARGUMENT_REGISTERS: [&str; 6] = ["rdi", "rsi", "rdx", "r10", "r8", "r9"];
+/// The registers the first `int` and `tof` arguments of a call are passed in, in order
+pub const INTEGER_ARGUMENT_REGISTERS: [&str; 6] = ["rdi", "rsi", "rdx", "rcx", "r8", "r9"];
+
+/// The registers the first `flt` arguments of a call are passed in, in order
+pub const FLOAT_ARGUMENT_REGISTERS: [&str; 8] = ["xmm0", "xmm1", "xmm2", "xmm3", "xmm4", "xmm5", "xmm6", "xmm7"];
+
+/// The register a call keeps the stack pointer in while it lines the stack up for `call`
+///
+/// This is a callee-saved register, so every subroutine saves it before using it
+pub const CALL_FRAME_REGISTER: &str = "rbx";
 
 
 pub mod core_utils {
-    use crate::type_traits::slice::StrSlice;
+    use crate::assembler::labels::LabelAllocator;
 
 
-    pub fn get_all() -> Vec<String> {
+    pub fn get_all(labels: &mut LabelAllocator) -> Vec<String> {
         let mut result: Vec<String> = Vec::new();
 
         // Append CMP lib
-        result.append(&mut CMP.to_string_vec());
+        result.append(&mut get_comparisons(labels));
+
+        // Append arithmetic lib
+        result.append(&mut get_arithmetic(labels));
+
+        // Append the runtime errors
+        result.append(&mut get_runtime_errors(labels));
 
         return result
     }
 
-    pub const CMP: &[&str] = &[
-        "cmp_eq:",
-        "  cmp rdi, rsi",
-        "  je .eq",
-        "  jmp .neq",
-        ".eq:",
-        "  mov rax, 1",
-        "  jmp .end",
-        ".neq:",
-        "  mov rax, 0",
-        "  jmp .end",
-        ".end:",
-        "  ret",
-        "",
-        "cmp_neq:",
-        "  cmp rdi, rsi",
-        "  jne .eq",
-        "  jmp .neq",
-        ".eq:",
-        "  mov rax, 1",
-        "  jmp .end",
-        ".neq:",
-        "  mov rax, 0",
-        "  jmp .end",
-        ".end:",
-        "  ret",
-        "cmp_gt:",
-        "  cmp rdi, rsi",
-        "  jg .eq",
-        "  jmp .neq",
-        ".eq:",
-        "  mov rax, 1",
-        "  jmp .end",
-        ".neq:",
-        "  mov rax, 0",
-        "  jmp .end",
-        ".end:",
-        "  ret",
-        "",
-        "cmp_geq:",
-        "  cmp rdi, rsi",
-        "  jge .eq",
-        "  jmp .neq",
-        ".eq:",
-        "  mov rax, 1",
-        "  jmp .end",
-        ".neq:",
-        "  mov rax, 0",
-        "  jmp .end",
-        ".end:",
-        "  ret",
-        "",
-        "cmp_lt:",
-        "  cmp rdi, rsi",
-        "  jl .eq",
-        "  jmp .neq",
-        ".eq:",
-        "  mov rax, 1",
-        "  jmp .end",
-        ".neq:",
-        "  mov rax, 0",
-        "  jmp .end",
-        ".end:",
-        "  ret",
-        "",
-        "cmp_leq:",
-        "  cmp rdi, rsi",
-        "  jle .eq",
-        "  jmp .neq",
-        ".eq:",
-        "  mov rax, 1",
-        "  jmp .end",
-        ".neq:",
-        "  mov rax, 0",
-        "  jmp .end",
-        ".end:",
-        "  ret",
-        "",
-    ];
+    /// The message written to stderr when a program divides by zero
+    pub const DIVISION_BY_ZERO_MESSAGE: &str = "error: attempted to divide by zero";
+
+    /// The status a program exits with when it hits a runtime error
+    pub const RUNTIME_ERROR_EXIT_STATUS: i64 = 101;
+
+    /// Gets every runtime error trap, where each trap writes its message to stderr and exits
+    /// with `RUNTIME_ERROR_EXIT_STATUS`
+    pub fn get_runtime_errors(labels: &mut LabelAllocator) -> Vec<String> {
+        let message = labels.helper("division_by_zero_message");
+
+        return vec![
+            format!("section .data"),
+            format!("{}: db \"{}\", 10", message, DIVISION_BY_ZERO_MESSAGE),
+            format!("section .text"),
+            format!(""),
+            format!("{}:", labels.helper("division_by_zero_trap")),
+            format!("  mov rax, 1"),
+            format!("  mov rdi, 2"),
+            format!("  lea rsi, [rel {}]", message),
+            format!("  mov rdx, {}", DIVISION_BY_ZERO_MESSAGE.len() + 1),
+            format!("  syscall"),
+            format!("  mov rax, 60"),
+            format!("  mov rdi, {}", RUNTIME_ERROR_EXIT_STATUS),
+            format!("  syscall"),
+            format!(""),
+        ]
+    }
+
+    /// Signed integer division of `rax` by `rdi`, leaving the quotient in `rax` and the
+    /// remainder in `rdx`
+    ///
+    /// Dividing by zero jumps to `division_by_zero_trap`, and dividing the smallest `int` by -1
+    /// wraps around instead of faulting
+    pub fn get_arithmetic(labels: &mut LabelAllocator) -> Vec<String> {
+        let negate = labels.allocate("int_divide_negate");
+
+        return vec![
+            format!("{}:", labels.helper("int_divide")),
+            format!("  test rdi, rdi"),
+            format!("  jz {}", labels.helper("division_by_zero_trap")),
+            format!("  cmp rdi, -1"),
+            format!("  je {}", negate),
+            format!("  cqo"),
+            format!("  idiv rdi"),
+            format!("  ret"),
+            format!("{}:", negate),
+            format!("  neg rax"),
+            format!("  xor rdx, rdx"),
+            format!("  ret"),
+            format!(""),
+        ]
+    }
+
+    /// The `cmp_` helpers, which each compare `rdi` to `rsi` and leave 1 in `rax` if the
+    /// comparison is true (or 0 if not)
+    pub fn get_comparisons(labels: &mut LabelAllocator) -> Vec<String> {
+        let mut result: Vec<String> = Vec::new();
+
+        for (name, jump_instruction) in [("cmp_eq", "je"), ("cmp_neq", "jne"), ("cmp_gt", "jg"), ("cmp_geq", "jge"), ("cmp_lt", "jl"), ("cmp_leq", "jle")] {
+            let is_true = labels.allocate(&format!("{}_true", name));
+
+            result.append(&mut vec![
+                format!("{}:", labels.helper(name)),
+                format!("  cmp rdi, rsi"),
+                format!("  {} {}", jump_instruction, is_true),
+                format!("  mov rax, 0"),
+                format!("  ret"),
+                format!("{}:", is_true),
+                format!("  mov rax, 1"),
+                format!("  ret"),
+                format!(""),
+            ]);
+        }
+
+        return result
+    }
 }
@@ -1,25 +1,89 @@
 use thiserror::Error;
 
+use crate::diagnostics::{Diagnostic, ToDiagnostic};
+use crate::optimizer::span::Span;
 
+
+/// Errors found while assembling the token tree
+///
+/// Errors that come from a statement or equation in the source keep its span.
+/// `ValueRetrievedIsNotATerm`, `ImproperUseOfTypesTranslator` and `IncorrectAssignmentFormatting`
+/// are only ever caused by the Assembler being misused, so there is no source to point at
 #[derive(Error, Debug)]
 pub enum AssemblerError {
     #[error("This token is not yet supported")]
-    TokenNotSupported,
-    #[error("The value attempting to the retrieved through Assignment.term_to_assembly_value().
-             Ensure this Assignment is either a CONST() or a TERM()")]
+    TokenNotSupported { span: Span },
+    #[error("The value attempting to the retrieved through Assignment.term_to_assembly_value() is not a term")]
     ValueRetrievedIsNotATerm,
-    #[error("The assignment you are trying to convert to an assembly instruction is not a valid branch.
-             Ensure you are using either an Assignment::INTEGER or Assignment::FLOAT branch")]
-    IncorrectAssignmentAttemptedToConvert,
-    #[error("This evaluation could not be parsed.
-             Ensure you are using a numerical value in the evaluation")]
-    CouldNotParseEvaluation,
-    #[error("The Assembler's types_translator was misused\n
-             Ensure you are only using this with complex Assignments")]
+    #[error("The assignment you are trying to convert to an assembly instruction is not a valid branch")]
+    IncorrectAssignmentAttemptedToConvert { span: Span },
+    #[error("This evaluation could not be parsed")]
+    CouldNotParseEvaluation { span: Span },
+    #[error("The Assembler's types_translator was misused")]
     ImproperUseOfTypesTranslator,
-    #[error("An assignment was incorrectly formatted\n
-             This is most commonly caused by mixing value types in equations")]
+    #[error("An assignment was incorrectly formatted")]
     IncorrectAssignmentFormatting,
-    #[error("A branch in a cmp was not a comparison")]
-    AssignmentInComparisonNotComparison,
+    #[error("the `{operator}` operator can only be used on `int` values")]
+    IntegerOnlyOperator { operator: String, span: Span },
+    #[error("A `break` or `continue` was not inside a loop")]
+    NoEnclosingLoop { span: Span },
+} impl AssemblerError {
+    /// The error code shown alongside this error when it's rendered
+    pub fn code(&self) -> &'static str { match self {
+        Self::TokenNotSupported { .. }                     => { "E0200" }
+        Self::ValueRetrievedIsNotATerm                     => { "E0201" }
+        Self::IncorrectAssignmentAttemptedToConvert { .. } => { "E0202" }
+        Self::CouldNotParseEvaluation { .. }               => { "E0203" }
+        Self::ImproperUseOfTypesTranslator                 => { "E0204" }
+        Self::IncorrectAssignmentFormatting                => { "E0205" }
+        Self::IntegerOnlyOperator { .. }                   => { "E0207" }
+        Self::NoEnclosingLoop { .. }                       => { "E0209" }
+    }}
+
+    /// The span of the source text that caused this error, if there is one
+    pub fn span(&self) -> Option<Span> { match self {
+        Self::TokenNotSupported { span }                     => { Some(*span) }
+        Self::IncorrectAssignmentAttemptedToConvert { span } => { Some(*span) }
+        Self::CouldNotParseEvaluation { span }               => { Some(*span) }
+        Self::IntegerOnlyOperator { span, .. }               => { Some(*span) }
+        Self::NoEnclosingLoop { span }                       => { Some(*span) }
+                                                           _ => { None }
+    }}
+
+    /// What to write under the span of this error
+    pub fn label(&self) -> String { match self {
+        Self::TokenNotSupported { .. }                     => { "can't be assembled here".into() }
+        Self::IncorrectAssignmentAttemptedToConvert { .. } => { "not a number".into() }
+        Self::CouldNotParseEvaluation { .. }               => { "not an `int` or `flt` equation".into() }
+        Self::IntegerOnlyOperator { .. }                   => { "used on a `flt` here".into() }
+        Self::NoEnclosingLoop { .. }                       => { "not inside a loop".into() }
+                                                         _ => { String::new() }
+    }}
+
+    /// A suggestion on how to fix this error, if there is one
+    pub fn help(&self) -> Option<&'static str> { match self {
+        Self::ValueRetrievedIsNotATerm                     => { Some("ensure this Assignment is either a CONST() or a TERM()") }
+        Self::IncorrectAssignmentAttemptedToConvert { .. } => { Some("ensure you are using either an Assignment::INTEGER or Assignment::FLOAT branch") }
+        Self::CouldNotParseEvaluation { .. }               => { Some("ensure you are using a numerical value in the evaluation") }
+        Self::ImproperUseOfTypesTranslator                 => { Some("ensure you are only using this with complex Assignments") }
+        Self::IncorrectAssignmentFormatting                => { Some("this is most commonly caused by mixing value types in equations") }
+                                                         _ => { None }
+    }}
+}
+
+impl ToDiagnostic for AssemblerError {
+    fn to_diagnostic(&self) -> Diagnostic {
+        let mut diagnostic = Diagnostic::error(&self.to_string())
+            .with_code(self.code());
+
+        if let Some(span) = self.span() {
+            diagnostic = diagnostic.with_label(span, &self.label());
+        }
+
+        if let Some(help) = self.help() {
+            diagnostic = diagnostic.with_help(help);
+        }
+
+        return diagnostic
+    }
 }
@@ -0,0 +1,69 @@
+use std::collections::{HashMap, HashSet};
+
+
+/// Hands out every label the Assembler writes, so no two labels in a program are ever the same
+///
+/// Subroutines keep their own names as labels (so they can be called from C), which are
+/// reserved before anything else is allocated. Every other label is named after what it's for
+/// and numbered, skipping any label that's already taken
+///
+/// # Examples
+///
+/// ```rust
+/// let mut labels = LabelAllocator::init();
+/// labels.reserve("main");
+///
+/// // "repeat_0", then "repeat_1"
+/// let first_loop  = labels.allocate("repeat");
+/// let second_loop = labels.allocate("repeat");
+///
+/// // "int_divide" every time
+/// let int_divide = labels.helper("int_divide");
+/// ```
+pub struct LabelAllocator {
+    taken: HashSet<String>,
+    counters: HashMap<String, usize>,
+    helpers: HashMap<String, String>,
+
+} impl LabelAllocator {
+    pub fn init() -> Self { Self {
+        taken: HashSet::new(),
+        counters: HashMap::new(),
+        helpers: HashMap::new(),
+    }}
+
+    /// Marks `label` as taken, so it's never allocated
+    pub fn reserve(&mut self, label: &str) {
+        self.taken.insert(label.to_string());
+    }
+
+    /// Allocates a new label named `<name>_<number>`
+    pub fn allocate(&mut self, name: &str) -> String {
+        let counter = self.counters.entry(name.to_string()).or_insert(0);
+
+        let mut label = format!("{}_{}", name, counter);
+        while self.taken.contains(&label) {
+            *counter += 1;
+            label = format!("{}_{}", name, counter);
+        }
+        *counter += 1;
+
+        self.taken.insert(label.clone());
+        return label
+    }
+
+    /// The label of the runtime helper `name`, which is the same every time it's asked for
+    ///
+    /// This is `name` itself unless a subroutine has already taken it
+    pub fn helper(&mut self, name: &str) -> String {
+        if let Some(label) = self.helpers.get(name) {
+            return label.clone()
+        }
+
+        let label = if self.taken.contains(name) { self.allocate(name) } else { name.to_string() };
+        self.taken.insert(label.clone());
+        self.helpers.insert(name.to_string(), label.clone());
+
+        return label
+    }
+}
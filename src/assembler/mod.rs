@@ -1,8 +1,9 @@
 use std::vec;
 
 use crate::tokenizer::{
-    conditional_loop::ConditionalLoop, conditional_statement::ConditionalStatement, declaration::Declaration, enumerators::{Assignment, ComparisonOperator}, function::{Function, Return}, reassignment::Reassignment, structures::VariableHistory, Token
+    conditional_loop::ConditionalLoop, conditional_statement::ConditionalStatement, declaration::{Declaration, DataType}, function::{Function, Return}, reassignment::Reassignment, structures::VariableHistory, terminating_loop::TerminatingLoop, loop_control::{Break, Continue}, Token
 };
+use crate::optimizer::span::Span;
 
 
 #[allow(dead_code)]
@@ -15,22 +16,32 @@ pub mod types_translator;
 
 #[allow(dead_code)]
 pub mod data;
-    use data::FUNCTION_ARGUMENT_REGISTERS;
+    use data::CALL_FRAME_REGISTER;
     use data::core_utils;
 
+#[allow(dead_code)]
+pub mod labels;
+    use labels::LabelAllocator;
+
+#[allow(dead_code)]
+pub mod calling_convention;
+    use calling_convention::{ArgumentLocation, locate_arguments};
+
 
 pub struct Assembler {
     pub instructions: Vec<String>,
 
-    conditional_loop_counter: usize,
-    conditional_statement_counter: usize,
+    labels: LabelAllocator,
+    epilogue_label: String,
+    enclosing_loops: Vec<LoopLabels>,
 }
 impl Assembler {
     pub fn init() -> Self { Self {
         instructions: Vec::new(),
 
-        conditional_loop_counter: 0,
-        conditional_statement_counter: 0,
+        labels: LabelAllocator::init(),
+        epilogue_label: String::new(),
+        enclosing_loops: Vec::new(),
     }}
 
     pub fn generate_instructions(&mut self, token_tree: &Vec<Token>) -> Result<(), AssemblerError> {
@@ -46,12 +57,18 @@ impl Assembler {
             "",
         ].iter().map(|x| x.to_string()).collect();
 
+        // Subroutines keep their names, so no other label can take them
+        self.labels.reserve("_start");
+        for token in token_tree.iter() { if let Token::Function(function) = token {
+            self.labels.reserve(&function.name);
+        }}
+
         // Write the basic utilities
-        program_instructions.append(&mut core_utils::get_all());
+        program_instructions.append(&mut core_utils::get_all(&mut self.labels));
 
         // Iterate over each token and translate it accordingly
         for token in token_tree.iter() { match token {
-            Token::Function(function) => program_instructions.append(&mut self.assemble_function(function)),
+            Token::Function(function) => program_instructions.append(&mut self.assemble_function(function)?),
             _ => {}
         }}
 
@@ -60,263 +77,335 @@ impl Assembler {
         return Ok(())
     }
 
-    fn assemble_function(&mut self, function: &Function) -> Vec<String> {
-        // Function start
+    fn assemble_function(&mut self, function: &Function) -> Result<Vec<String>, AssemblerError> {
+        // Every `expose` in this function jumps to its epilogue
+        self.epilogue_label = self.labels.allocate(&format!("{}_epilogue", function.name));
+
+        // Function start, linking the frame to its caller's (so backtraces can walk it) before
+        // saving the callee-saved registers this function uses
         let mut function_instructions: Vec<String> = vec![
+            format!("global {}", function.name),
             format!("{}:", function.name),
             format!("  push rbp"),
             format!("  mov rbp, rsp"),
+            format!("  push {}", CALL_FRAME_REGISTER),
+            format!("  sub rsp, {}", frame_size(&function.variable_history)),
             format!(""),
         ];
 
-        // Initialize arguments
-        for (argument_index, argument) in function.arguments.iter().enumerate() {
+        // Copy the arguments into their variables
+        let argument_types: Vec<DataType> = function.arguments.iter().map(|x| x.data_type.clone()).collect();
+        for (argument, argument_location) in function.arguments.iter().zip(locate_arguments(&argument_types)) {
             let variable_location_index = function.variable_history.find_variable(&argument.name).unwrap();
-            let variable_location = (variable_location_index+1) * function.variable_history.step;
-            let active_register = FUNCTION_ARGUMENT_REGISTERS[argument_index];
+            let variable_location = function.variable_history.stack_offset(variable_location_index);
 
-            function_instructions.append(&mut vec![
-                format!("  sub rsp, 8"),
-                format!("  mov QWORD [rbp-{}], {}", variable_location, active_register),
-                format!("  push {}", active_register),
-            ]);
+            match argument_location {
+                ArgumentLocation::IntegerRegister(register) => { function_instructions.append(&mut vec![
+                    format!("  mov QWORD [rbp-{}], {}", variable_location, register),
+                ]);}
+                ArgumentLocation::FloatRegister(register) => { function_instructions.append(&mut vec![
+                    format!("  movsd QWORD [rbp-{}], {}", variable_location, register),
+                ]);}
+                // Stack arguments sit above the saved rbp and the return address
+                ArgumentLocation::Stack(stack_index) => { function_instructions.append(&mut vec![
+                    format!("  mov rax, QWORD [rbp+{}]", 16 + stack_index * 8),
+                    format!("  mov QWORD [rbp-{}], rax", variable_location),
+                ]);}
+            }
         }
 
         // Assemble the functionality
-        for token in function.functionaliy.iter() { match token {
-            Token::Declaration(declaration) => {
-                function_instructions.append(&mut self.assemble_declaration(&function.variable_history, declaration));
-            }
-            Token::Reassignment(reassignment) => {
-                function_instructions.append(&mut self.assemble_reassignment(&function.variable_history, reassignment));
-            }
-            Token::Return(return_statement) => {
-                function_instructions.append(&mut self.assemble_return(&function.variable_history, return_statement));
-            }
-            Token::ConditionalStatement(conditional_statement) => {
-                function_instructions.append(&mut self.assemble_conditional_statement(&function.variable_history, conditional_statement).unwrap());
-            }
-            Token::ConditionalLoop(conditional_loop) => {
-                function_instructions.append(&mut self.assemble_conditional_loop(&function.variable_history, conditional_loop));
-            }
-            _ => {}
-        }}
+        function_instructions.append(&mut self.assemble_block(&function.variable_history, &function.functionaliy)?);
 
         // Begin the function's end
         function_instructions.append(&mut vec![
-                format!(".end:"),
+            format!("{}:", self.epilogue_label),
         ]);
 
-        // Return the active registers to their original values
-        for (argument_index, _) in function.arguments.iter().enumerate() {
-            let active_register = FUNCTION_ARGUMENT_REGISTERS[argument_index];
-
-            function_instructions.append(&mut vec![
-                format!("  pop {}", active_register),
-            ]);
-        }
-
-        // Reset the stack frame and return
+        // Reset the stack frame, restore the callee-saved registers and return
         function_instructions.append(&mut vec![
-            format!("  mov rsp, rbp"),
+            format!("  lea rsp, [rbp-8]"),
+            format!("  pop {}", CALL_FRAME_REGISTER),
             format!("  pop rbp"),
             format!("  ret"),
             format!(""),
         ]);
 
         // Return the result
-        return function_instructions
+        return Ok(function_instructions)
+    }
+
+    /// Assembles every statement in `block`, in order
+    fn assemble_block(&mut self, variable_history: &VariableHistory, block: &[Token]) -> Result<Vec<String>, AssemblerError> {
+        let mut appended_instructions: Vec<String> = Vec::new();
+
+        for token in block.iter() {
+            appended_instructions.append(&mut self.assemble_statement(variable_history, token)?);
+        }
+
+        return Ok(appended_instructions)
     }
 
-    fn assemble_conditional_loop(&mut self, variable_history: &VariableHistory, conditional_loop: &ConditionalLoop) -> Vec<String> {
+    /// Assembles a single statement, along with every statement nested inside of it
+    fn assemble_statement(&mut self, variable_history: &VariableHistory, token: &Token) -> Result<Vec<String>, AssemblerError> { match token {
+        Token::Declaration(declaration)                   => { return self.assemble_declaration(variable_history, declaration)                     }
+        Token::Reassignment(reassignment)                 => { return self.assemble_reassignment(variable_history, reassignment)                   }
+        Token::Return(return_statement)                   => { return self.assemble_return(variable_history, return_statement)                     }
+        Token::ConditionalStatement(conditional_statement) => { return self.assemble_conditional_statement(variable_history, conditional_statement) }
+        Token::ConditionalLoop(conditional_loop)          => { return self.assemble_conditional_loop(variable_history, conditional_loop)           }
+        Token::TerminatingLoop(terminating_loop)          => { return self.assemble_terminating_loop(variable_history, terminating_loop)           }
+        Token::Break(break_statement)                     => { return self.assemble_break(break_statement)                                         }
+        Token::Continue(continue_statement)               => { return self.assemble_continue(continue_statement)                                   }
+        Token::Function(function)                         => { return Err(AssemblerError::TokenNotSupported { span: function.span })               }
+    }}
+
+    fn assemble_conditional_loop(&mut self, variable_history: &VariableHistory, conditional_loop: &ConditionalLoop) -> Result<Vec<String>, AssemblerError> {
         let mut appended_instructions: Vec<String> = Vec::new();
 
-        let branch_name = format!(".loop{}", self.conditional_loop_counter);
+        let head_label = self.labels.allocate("repeat");
+        let end_label  = self.labels.allocate("repeat_end");
 
         // Assemble the header
         appended_instructions.append(&mut vec![
-            format!("{}:", branch_name),
+            format!("{}:", head_label),
         ]);
-        if let Assignment::CMP(first_assignment, operator, second_assignment) = &conditional_loop.condition {
-            appended_instructions.append(&mut vec![
-                first_assignment.to_assembly_instructions("rdi", variable_history).unwrap(),
-                second_assignment.to_assembly_instructions("rsi", variable_history).unwrap(),
-            ].concat());
-            appended_instructions.append(&mut vec![
-                format!("  cmp rdi, rsi")
-            ]);
-
-            match operator.negation() {
-                ComparisonOperator::EQ  => { appended_instructions.append(&mut vec![
-                    format!("  je {}_end", branch_name),
-                ]);}
-                ComparisonOperator::NEQ => { appended_instructions.append(&mut vec![
-                    format!("  jne {}_end", branch_name),
-                ]);}
-                ComparisonOperator::GT  => { appended_instructions.append(&mut vec![
-                    format!("  jg {}_end", branch_name),
-                ]);}
-                ComparisonOperator::GEQ => { appended_instructions.append(&mut vec![
-                    format!("  jge {}_end", branch_name),
-                ]);}
-                ComparisonOperator::LT  => { appended_instructions.append(&mut vec![
-                    format!("  jl {}_end", branch_name),
-                ]);}
-                ComparisonOperator::LEQ => { appended_instructions.append(&mut vec![
-                    format!("  jle {}_end", branch_name),
-                ]);}
-            }
-        }
+        // Leave the loop once the condition is false
+        appended_instructions.append(&mut conditional_loop.condition.to_condition_instructions(false, &end_label, variable_history, &mut self.labels)?);
 
         // Assemble the functionality
-        for token in &conditional_loop.functionality { match token {
-            Token::Declaration(declaration) => {
-                appended_instructions.append(&mut self.assemble_declaration(variable_history, declaration));
-            }
-            Token::Reassignment(reassignment) => {
-                appended_instructions.append(&mut self.assemble_reassignment(variable_history, reassignment));
-            }
-            Token::Return(return_statement) => {
-                appended_instructions.append(&mut self.assemble_return(variable_history, return_statement));
-            }
-            _ => {}
-        }}
+        self.enclosing_loops.push(LoopLabels {
+            label: conditional_loop.label.clone(),
+            continue_label: head_label.clone(),
+            break_label: end_label.clone(),
+        });
+        let functionality = self.assemble_block(variable_history, &conditional_loop.functionality);
+        self.enclosing_loops.pop();
+        appended_instructions.append(&mut functionality?);
 
         // Ensure the loop jumps back to the beginning
         appended_instructions.append(&mut vec![
-            format!("  jmp {}", branch_name)
+            format!("  jmp {}", head_label)
+        ]);
+
+        // Define the end of the loop
+        appended_instructions.append(&mut vec![
+            format!("{}:", end_label)
+        ]);
+
+        return Ok(appended_instructions)
+    }
+
+    /// Assembles a counted `loop`
+    ///
+    /// `to` and `step` are worked out again before every pass (like the condition of a
+    /// `repeat`). The loop counts up while `step` is positive and down while it's negative, and
+    /// ends once its variable reaches or passes `to`
+    fn assemble_terminating_loop(&mut self, variable_history: &VariableHistory, terminating_loop: &TerminatingLoop) -> Result<Vec<String>, AssemblerError> {
+        let mut appended_instructions: Vec<String> = Vec::new();
+
+        let head_label       = self.labels.allocate("loop");
+        let count_down_label = self.labels.allocate("loop_count_down");
+        let body_label       = self.labels.allocate("loop_body");
+        let next_label       = self.labels.allocate("loop_next");
+        let end_label        = self.labels.allocate("loop_end");
+
+        let variable_location = variable_history.stack_offset(terminating_loop.location);
+
+        // Start the loop variable at `from`
+        appended_instructions.append(&mut terminating_loop.from.to_assembly_instructions("rdi", variable_history, &mut self.labels)?);
+        appended_instructions.append(&mut vec![
+            format!("  mov QWORD [rbp-{}], rdi", variable_location),
+        ]);
+
+        // Assemble the header, leaving the loop once the variable reaches `to`
+        appended_instructions.append(&mut vec![
+            format!("{}:", head_label),
+        ]);
+        appended_instructions.append(&mut terminating_loop.step.to_assembly_instructions("rdi", variable_history, &mut self.labels)?);
+        appended_instructions.append(&mut vec![
+            format!("  push rdi"),
+        ]);
+        appended_instructions.append(&mut terminating_loop.to.to_assembly_instructions("rdi", variable_history, &mut self.labels)?);
+        appended_instructions.append(&mut vec![
+            format!("  pop rsi"),
+            format!("  mov rax, QWORD [rbp-{}]", variable_location),
+            format!("  test rsi, rsi"),
+            format!("  js {}", count_down_label),
+            format!("  cmp rax, rdi"),
+            format!("  jge {}", end_label),
+            format!("  jmp {}", body_label),
+            format!("{}:", count_down_label),
+            format!("  cmp rax, rdi"),
+            format!("  jle {}", end_label),
+            format!("{}:", body_label),
+        ]);
+
+        // Assemble the functionality
+        self.enclosing_loops.push(LoopLabels {
+            label: Some(terminating_loop.name.clone()),
+            continue_label: next_label.clone(),
+            break_label: end_label.clone(),
+        });
+        let functionality = self.assemble_block(variable_history, &terminating_loop.functionality);
+        self.enclosing_loops.pop();
+        appended_instructions.append(&mut functionality?);
+
+        // Step the loop variable and jump back to the beginning
+        appended_instructions.append(&mut vec![
+            format!("{}:", next_label),
+        ]);
+        appended_instructions.append(&mut terminating_loop.step.to_assembly_instructions("rdi", variable_history, &mut self.labels)?);
+        appended_instructions.append(&mut vec![
+            format!("  add QWORD [rbp-{}], rdi", variable_location),
+            format!("  jmp {}", head_label),
         ]);
 
         // Define the end of the loop
         appended_instructions.append(&mut vec![
-            format!("  {}_end:", branch_name)
+            format!("{}:", end_label),
         ]);
 
-        self.conditional_loop_counter += 1;
-        return appended_instructions;
+        return Ok(appended_instructions)
+    }
+
+    fn assemble_break(&mut self, break_statement: &Break) -> Result<Vec<String>, AssemblerError> {
+        let enclosing_loop = self.find_enclosing_loop(&break_statement.label, break_statement.span)?;
+
+        return Ok(vec![
+            format!("  jmp {}", enclosing_loop.break_label),
+        ])
+    }
+
+    fn assemble_continue(&mut self, continue_statement: &Continue) -> Result<Vec<String>, AssemblerError> {
+        let enclosing_loop = self.find_enclosing_loop(&continue_statement.label, continue_statement.span)?;
+
+        return Ok(vec![
+            format!("  jmp {}", enclosing_loop.continue_label),
+        ])
+    }
+
+    /// Finds the innermost loop being assembled labelled `label` (or the innermost loop if
+    /// there's no label), where `span` is the `break` or `continue` looking for it
+    fn find_enclosing_loop(&self, label: &Option<String>, span: Span) -> Result<&LoopLabels, AssemblerError> {
+        let enclosing_loop = match label {
+            Some(_) => { self.enclosing_loops.iter().rev().find(|x| x.label == *label) }
+            None    => { self.enclosing_loops.last() }
+        };
+
+        return enclosing_loop.ok_or(AssemblerError::NoEnclosingLoop { span })
     }
 
     fn assemble_conditional_statement(&mut self, variable_history: &VariableHistory, conditional_statement: &ConditionalStatement) -> Result<Vec<String>, AssemblerError> {
         let mut appended_instructions: Vec<String> = Vec::new();
 
-        let branch_name = format!(".cmp{}", self.conditional_statement_counter);
+        let branch_labels: Vec<String> = conditional_statement.condition_fields.iter()
+            .map(|_| self.labels.allocate("cmpr_branch"))
+            .collect();
+        let end_label = self.labels.allocate("cmpr_end");
 
         // Assemble the header
-        for (i, (condition_wrapped, _)) in conditional_statement.condition_fields.iter().enumerate() {
-
-            if let Some(condition) = condition_wrapped { if let Assignment::CMP(first_assignment, operator, second_assignment) = condition {
-                // put first and second values into registers
-                appended_instructions.append(&mut vec![
-                    first_assignment.to_assembly_instructions("rdi", variable_history).unwrap(),
-                    second_assignment.to_assembly_instructions("rsi", variable_history).unwrap(),
-                ].concat());
-                appended_instructions.append(&mut vec![
-                    format!("  cmp rdi, rsi")
-                ]);
-
-                match operator {
-                    ComparisonOperator::EQ  => { appended_instructions.append(&mut vec![
-                        format!("  je {}_br{}", branch_name, i),
-                    ]);}
-                    ComparisonOperator::NEQ => { appended_instructions.append(&mut vec![
-                        format!("  jne {}_br{}", branch_name, i),
-                    ]);}
-                    ComparisonOperator::GT  => { appended_instructions.append(&mut vec![
-                        format!("  jg {}_br{}", branch_name, i),
-                    ]);}
-                    ComparisonOperator::GEQ => { appended_instructions.append(&mut vec![
-                        format!("  jge {}_br{}", branch_name, i),
-                    ]);}
-                    ComparisonOperator::LT  => { appended_instructions.append(&mut vec![
-                        format!("  jl {}_br{}", branch_name, i),
-                    ]);}
-                    ComparisonOperator::LEQ => { appended_instructions.append(&mut vec![
-                        format!("  jle {}_br{}", branch_name, i),
-                    ]);}
-                }
-            } else { return Err(AssemblerError::AssignmentInComparisonNotComparison); } }
+        for ((condition_wrapped, _), branch_label) in conditional_statement.condition_fields.iter().zip(branch_labels.iter()) {
+
+            if let Some(condition) = condition_wrapped {
+                // jump to this branch if its condition is true
+                appended_instructions.append(&mut condition.to_condition_instructions(true, branch_label, variable_history, &mut self.labels)?);
+            }
             else { appended_instructions.append(&mut vec![
-                format!("  jmp {}_br{}", branch_name, i)
+                format!("  jmp {}", branch_label)
             ]);}
         }
         appended_instructions.append(&mut vec![
-            format!("  jmp {}_end", branch_name)
+            format!("  jmp {}", end_label)
         ]);
 
         // Assemble the branches
-        for (i, (_, token_tree)) in conditional_statement.condition_fields.iter().enumerate() {
+        for ((_, token_tree), branch_label) in conditional_statement.condition_fields.iter().zip(branch_labels.iter()) {
             // declare the branch
             appended_instructions.append(&mut vec![
-                format!("{}_br{}:", branch_name, i)
+                format!("{}:", branch_label)
             ]);
 
-            // assemble its declaration
-            for token in token_tree.iter() { match token {
-                Token::Declaration(declaration) => {
-                    appended_instructions.append(&mut self.assemble_declaration(variable_history, declaration));
-                }
-                Token::Reassignment(reassignment) => {
-                    appended_instructions.append(&mut self.assemble_reassignment(variable_history, reassignment));
-                }
-                Token::Return(return_statement) => {
-                    appended_instructions.append(&mut self.assemble_return(variable_history, return_statement));
-                }
-                _ => {}
-            }}
+            // assemble its functionality
+            appended_instructions.append(&mut self.assemble_block(variable_history, token_tree)?);
 
             // conclude it by jumping to the end
             appended_instructions.append(&mut vec![
-                format!("  jmp {}_end", branch_name)
+                format!("  jmp {}", end_label)
             ]);
         }
 
         // Assemble the end branch
         appended_instructions.append(&mut vec![
-            format!("{}_end:", branch_name)
+            format!("{}:", end_label)
         ]);
 
-        self.conditional_statement_counter += 1;
         return Ok(appended_instructions)
     }
 
-    fn assemble_declaration(&self, stack_memory: &VariableHistory, declaration: &Declaration) -> Vec<String> {
-        let assignment_instructions = declaration.value.clone().to_assembly_instructions("rdi", stack_memory);
+    fn assemble_declaration(&mut self, stack_memory: &VariableHistory, declaration: &Declaration) -> Result<Vec<String>, AssemblerError> {
+        let assignment_instructions = declaration.value.clone().to_assembly_instructions("rdi", stack_memory, &mut self.labels)?;
         let appended_instructions: Vec<String> = vec![
-            vec![
-                format!("  sub rsp, {}", stack_memory.step),
-            ],
-            assignment_instructions.unwrap(),
+            assignment_instructions,
             vec![
                 format!("  mov rax, rdi"),
-                format!("  mov QWORD [rbp-{}], rax", (declaration.location+1) * stack_memory.step),
+                format!("  mov QWORD [rbp-{}], rax", stack_memory.stack_offset(declaration.location)),
             ],
         ].concat().iter().map(|x| x.to_string()).collect();
-        return appended_instructions
+        return Ok(appended_instructions)
     }
 
-    fn assemble_reassignment(&self, variable_history: &VariableHistory, reassignment: &Reassignment) -> Vec<String> {
-        let assignment_instructions = reassignment.new_assignment.clone().to_assembly_instructions("rdi", variable_history);
-
-        let variable_location = variable_history.find_variable(&reassignment.name).unwrap();
+    fn assemble_reassignment(&mut self, variable_history: &VariableHistory, reassignment: &Reassignment) -> Result<Vec<String>, AssemblerError> {
+        let assignment_instructions = reassignment.new_assignment.clone().to_assembly_instructions("rdi", variable_history, &mut self.labels)?;
 
         let appended_instructions: Vec<String> = vec![
-            assignment_instructions.unwrap(),
+            assignment_instructions,
             vec![
                 format!("  mov rax, rdi"),
-                format!("  mov QWORD [rbp-{}], rax", (variable_location+1) * variable_history.step),
+                format!("  mov QWORD [rbp-{}], rax", variable_history.stack_offset(reassignment.location)),
             ],
         ].concat().iter().map(|x| x.to_string()).collect();
-        return appended_instructions
+        return Ok(appended_instructions)
     }
 
-    fn assemble_return(&self, variable_history: &VariableHistory, return_statement: &Return) -> Vec<String> {
-        let mut assignment_instructions = return_statement.assignment.to_assembly_instructions("rdi", variable_history)
-            .unwrap();
+    fn assemble_return(&mut self, variable_history: &VariableHistory, return_statement: &Return) -> Result<Vec<String>, AssemblerError> {
+        let mut assignment_instructions = return_statement.assignment.to_assembly_instructions("rdi", variable_history, &mut self.labels)?;
 
         assignment_instructions.append(&mut vec![
             format!("  mov rax, rdi")
         ]);
 
-        return assignment_instructions
+        // `flt` values are returned in xmm0
+        if return_statement.assignment.evaluate_type(variable_history).is_float() { assignment_instructions.append(&mut vec![
+            format!("  movq xmm0, rax")
+        ]);}
+
+        // Leave the function from wherever this is
+        assignment_instructions.append(&mut vec![
+            format!("  jmp {}", self.epilogue_label)
+        ]);
+
+        return Ok(assignment_instructions)
     }
 }
+
+
+/// Where a `break` or `continue` inside a loop jumps to
+struct LoopLabels {
+    label: Option<String>,
+    continue_label: String,
+    break_label: String,
+}
+
+
+/// The number of bytes a function reserves below `rbp` for its variables
+///
+/// There's a slot for every variable in scope at once (variables of scopes that already ended
+/// share their slots), and the frame is padded so the stack is 16 byte aligned once it's
+/// reserved. Temporaries are pushed (and popped) on top of the frame
+/// while an equation is evaluated
+fn frame_size(variable_history: &VariableHistory) -> usize {
+    // The return address, rbp and CALL_FRAME_REGISTER are pushed before the frame is reserved
+    let pushed_bytes: usize = 24;
+
+    return (pushed_bytes + variable_history.slot_count() * variable_history.step).next_multiple_of(16) - pushed_bytes
+}
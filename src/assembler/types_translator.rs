@@ -1,10 +1,13 @@
+use crate::data::SyntaxElements;
 use crate::tokenizer::declaration::DataType;
-use crate::tokenizer::enumerators::{Assignment, ComparisonOperator, MathOperator};
+use crate::tokenizer::enumerators::{Assignment, ComparisonOperator, LogicalOperator, MathOperator, UnaryOperator};
 use crate::tokenizer::structures::VariableHistory;
 use crate::type_traits::integer::I64Extra;
 use crate::type_traits::float::F64Extra;
 
-use super::data::FUNCTION_ARGUMENT_REGISTERS;
+use super::data::CALL_FRAME_REGISTER;
+use super::labels::LabelAllocator;
+use super::calling_convention::{ArgumentLocation, count_stack_arguments, locate_arguments};
 use super::error::AssemblerError;
 
 
@@ -16,7 +19,16 @@ pub trait AssignmentToAssembly {
     ///
     /// Note that the rax register is reserved for performing arithmetic with the result of this
     /// function
-    fn to_assembly_instructions(&self, target_register: &str, variable_history: &VariableHistory) -> Result<Vec<String>, AssemblerError>;
+    ///
+    /// `labels` names the labels `and` and `or` jump between, and the runtime helpers called
+    fn to_assembly_instructions(&self, target_register: &str, variable_history: &VariableHistory, labels: &mut LabelAllocator) -> Result<Vec<String>, AssemblerError>;
+
+    /// Converts this assignment (a `tof` value) into a chain of branches that jumps to
+    /// `jump_label` if it evaluates to `jump_if`, and otherwise falls through
+    ///
+    /// `and` and `or` only evaluate their second term if the first did not already decide the
+    /// result
+    fn to_condition_instructions(&self, jump_if: bool, jump_label: &str, variable_history: &VariableHistory, labels: &mut LabelAllocator) -> Result<Vec<String>, AssemblerError>;
 
     /// Converts either `Self::INTEGER` or `Self::FLOAT` to its acocciated assembly value
     ///
@@ -24,14 +36,60 @@ pub trait AssignmentToAssembly {
     fn to_assembly_value(&self) -> Result<String, AssemblerError>;
 }
 
+/// The conditional jump instruction that jumps when `operator` is true of the last `cmp`
+fn jump_instruction(operator: &ComparisonOperator) -> &'static str { match operator {
+    ComparisonOperator::EQ  => { return "je"  }
+    ComparisonOperator::NEQ => { return "jne" }
+    ComparisonOperator::GT  => { return "jg"  }
+    ComparisonOperator::GEQ => { return "jge" }
+    ComparisonOperator::LT  => { return "jl"  }
+    ComparisonOperator::LEQ => { return "jle" }
+}}
+
+/// Whether a comparison between these two terms has to be done on floats
+fn is_float_comparison(first_term_assignment: &Assignment, second_term_assignment: &Assignment, variable_history: &VariableHistory) -> bool {
+    return first_term_assignment.evaluate_type(variable_history).is_float() || second_term_assignment.evaluate_type(variable_history).is_float()
+}
+
+/// Loads two float terms into `xmm0` and `xmm1` and compares them with `ucomisd`
+///
+/// `<` and `<=` compare the terms the other way around, so they set the flags as `>` and `>=`
+/// would
+fn float_comparison_instructions(first_term_assignment: &Assignment, operator: &ComparisonOperator, second_term_assignment: &Assignment, variable_history: &VariableHistory, labels: &mut LabelAllocator) -> Result<Vec<String>, AssemblerError> {
+    // The first term is kept on the stack while the second is evaluated
+    let mut returned_instructions: Vec<String> = [
+        first_term_assignment.to_assembly_instructions("rax", variable_history, labels)?,
+        vec![format!("  push rax")],
+        second_term_assignment.to_assembly_instructions("rax", variable_history, labels)?,
+        vec![format!("  movq xmm1, rax")],
+        vec![format!("  pop rax")],
+        vec![format!("  movq xmm0, rax")],
+    ].concat();
+
+    match operator {
+        ComparisonOperator::LT | ComparisonOperator::LEQ => { returned_instructions.append(&mut vec![
+            format!("  ucomisd xmm1, xmm0"),
+        ]);}
+        _ => { returned_instructions.append(&mut vec![
+            format!("  ucomisd xmm0, xmm1"),
+        ]);}
+    }
+
+    return Ok(returned_instructions)
+}
+
 impl AssignmentToAssembly for Assignment {
-    fn to_assembly_instructions(&self, target_register: &str, variable_history: &VariableHistory) -> Result<Vec<String>, AssemblerError> { match self {
-        Self::EVAL(first_term_assignment, operation, second_term_assignment) => { match self.evaluate_type(variable_history) {
+    fn to_assembly_instructions(&self, target_register: &str, variable_history: &VariableHistory, labels: &mut LabelAllocator) -> Result<Vec<String>, AssemblerError> { match self {
+        Self::EVAL(first_term_assignment, operation, second_term_assignment, span) => { match self.evaluate_type(variable_history) {
             DataType::INTEGER => {
                 // Convert the first and second terms into assembly
+                // The first term is kept on the stack while the second is evaluated, as the
+                // second term may be an equation of its own that needs rax
                 let mut returned_instructions: Vec<String> = vec![
-                    first_term_assignment.to_assembly_instructions("rax", variable_history).unwrap(),
-                    second_term_assignment.to_assembly_instructions("rdi", variable_history).unwrap(),
+                    first_term_assignment.to_assembly_instructions("rax", variable_history, labels)?,
+                    vec![format!("  push rax")],
+                    second_term_assignment.to_assembly_instructions("rdi", variable_history, labels)?,
+                    vec![format!("  pop rax")],
                 ].concat();
 
                 // Perform the operation
@@ -45,7 +103,30 @@ impl AssignmentToAssembly for Assignment {
                     MathOperator::MUL => { returned_instructions.append(&mut vec![
                         format!("  imul rax, rdi")
                     ]);}
-                    MathOperator::DIV => { return Err(AssemblerError::CouldNotParseEvaluation) }
+                    MathOperator::DIV => { returned_instructions.append(&mut vec![
+                        format!("  call {}", labels.helper("int_divide"))
+                    ]);}
+                    MathOperator::MOD => { returned_instructions.append(&mut vec![
+                        format!("  call {}", labels.helper("int_divide")),
+                        format!("  mov rax, rdx"),
+                    ]);}
+                    MathOperator::BAND => { returned_instructions.append(&mut vec![
+                        format!("  and rax, rdi")
+                    ]);}
+                    MathOperator::BOR => { returned_instructions.append(&mut vec![
+                        format!("  or rax, rdi")
+                    ]);}
+                    MathOperator::BXOR => { returned_instructions.append(&mut vec![
+                        format!("  xor rax, rdi")
+                    ]);}
+                    MathOperator::SHL => { returned_instructions.append(&mut vec![
+                        format!("  mov rcx, rdi"),
+                        format!("  shl rax, cl"),
+                    ]);}
+                    MathOperator::SHR => { returned_instructions.append(&mut vec![
+                        format!("  mov rcx, rdi"),
+                        format!("  sar rax, cl"),
+                    ]);}
                 }
 
                 // Place the result into the target register
@@ -57,12 +138,16 @@ impl AssignmentToAssembly for Assignment {
                 return Ok(returned_instructions)
             }
             DataType::FLOAT => {
-                // Convert the first term into assembly
+                // Convert the first and second terms into assembly
+                // The first term is kept on the stack while the second is evaluated, as the
+                // second term may be an equation of its own that needs xmm0
                 let mut returned_instructions: Vec<String> = vec![
-                    first_term_assignment.to_assembly_instructions("rax", variable_history).unwrap(),
-                    vec![format!("  movq xmm0, rax")],
-                    second_term_assignment.to_assembly_instructions("rax", variable_history).unwrap(),
+                    first_term_assignment.to_assembly_instructions("rax", variable_history, labels)?,
+                    vec![format!("  push rax")],
+                    second_term_assignment.to_assembly_instructions("rax", variable_history, labels)?,
                     vec![format!("  movq xmm1, rax")],
+                    vec![format!("  pop rax")],
+                    vec![format!("  movq xmm0, rax")],
                 ].concat();
 
                 // Perform the Operation
@@ -79,6 +164,7 @@ impl AssignmentToAssembly for Assignment {
                     MathOperator::DIV => { returned_instructions.append(&mut vec![
                         format!("  divsd xmm0, xmm1")
                     ]);}
+                    operation => { return Err(AssemblerError::IntegerOnlyOperator { operator: operation.to_symbol(), span: *span }) }
                 }
 
                 // Place the result into the target register
@@ -89,35 +175,70 @@ impl AssignmentToAssembly for Assignment {
                 // Return it
                 return Ok(returned_instructions)
             }
-            _ => {return Err(AssemblerError::CouldNotParseEvaluation)}
+            _ => {return Err(AssemblerError::CouldNotParseEvaluation { span: *span })}
         }}
 
-        Self::CMP(first_term_assignment, operator, second_term_assignment) => {
+        Self::CMP(first_term_assignment, operator, second_term_assignment, _) if is_float_comparison(first_term_assignment, second_term_assignment, variable_history) => {
+            let mut returned_instructions: Vec<String> = float_comparison_instructions(first_term_assignment, operator, second_term_assignment, variable_history, labels)?;
+
+            // Turn the flags into 1 or 0, where every comparison with NaN is false (other than
+            // not equal to)
+            match operator {
+                ComparisonOperator::EQ => { returned_instructions.append(&mut vec![
+                    format!("  sete al"),
+                    format!("  setnp cl"),
+                    format!("  and al, cl"),
+                ]);}
+                ComparisonOperator::NEQ => { returned_instructions.append(&mut vec![
+                    format!("  setne al"),
+                    format!("  setp cl"),
+                    format!("  or al, cl"),
+                ]);}
+                ComparisonOperator::GT | ComparisonOperator::LT => { returned_instructions.append(&mut vec![
+                    format!("  seta al"),
+                ]);}
+                ComparisonOperator::GEQ | ComparisonOperator::LEQ => { returned_instructions.append(&mut vec![
+                    format!("  setae al"),
+                ]);}
+            }
+
+            // Put the result into the target register
+            returned_instructions.append(&mut vec![
+                format!("  movzx {}, al", target_register),
+            ]);
+
+            return Ok(returned_instructions)
+        }
+
+        Self::CMP(first_term_assignment, operator, second_term_assignment, _) => {
             // Convert first and second terms
+            // The first term is kept on the stack while the second is evaluated
             let mut returned_instructions: Vec<String> = vec![
-                first_term_assignment.to_assembly_instructions("rdi", variable_history).unwrap(),
-                second_term_assignment.to_assembly_instructions("rsi", variable_history).unwrap(),
+                first_term_assignment.to_assembly_instructions("rax", variable_history, labels)?,
+                vec![format!("  push rax")],
+                second_term_assignment.to_assembly_instructions("rsi", variable_history, labels)?,
+                vec![format!("  pop rdi")],
             ].concat();
 
             // Run it through the associated cmp_ function to determine the result
             match operator {
                 ComparisonOperator::EQ  => {returned_instructions.append(&mut vec![
-                    format!("  call cmp_eq")
+                    format!("  call {}", labels.helper("cmp_eq"))
                 ])}
                 ComparisonOperator::NEQ => {returned_instructions.append(&mut vec![
-                    format!("  call cmp_neq")
+                    format!("  call {}", labels.helper("cmp_neq"))
                 ])}
                 ComparisonOperator::GT  => {returned_instructions.append(&mut vec![
-                    format!("  call cmp_gt")
+                    format!("  call {}", labels.helper("cmp_gt"))
                 ])}
                 ComparisonOperator::GEQ => {returned_instructions.append(&mut vec![
-                    format!("  call cmp_geq")
+                    format!("  call {}", labels.helper("cmp_geq"))
                 ])}
                 ComparisonOperator::LT  => {returned_instructions.append(&mut vec![
-                    format!("  call cmp_lt")
+                    format!("  call {}", labels.helper("cmp_lt"))
                 ])}
                 ComparisonOperator::LEQ => {returned_instructions.append(&mut vec![
-                    format!("  call cmp_leq")
+                    format!("  call {}", labels.helper("cmp_leq"))
                 ])}
             }
 
@@ -130,7 +251,79 @@ impl AssignmentToAssembly for Assignment {
             return Ok(returned_instructions)
         }
 
-        Self::INTEGER(returned_number) => {
+        Self::LOGIC(_, _, _, _) => {
+            let false_label = labels.allocate("logic_false");
+            let end_label   = labels.allocate("logic_end");
+
+            // Branch to the false case, otherwise the result is true
+            let mut returned_instructions: Vec<String> = self.to_condition_instructions(false, &false_label, variable_history, labels)?;
+            returned_instructions.append(&mut vec![
+                format!("  mov {}, 1", target_register),
+                format!("  jmp {}", end_label),
+                format!("{}:", false_label),
+                format!("  mov {}, 0", target_register),
+                format!("{}:", end_label),
+            ]);
+
+            return Ok(returned_instructions)
+        }
+
+        Self::UNARY(operator, term_assignment, span) => {
+            // Convert the term straight into the target register
+            let mut returned_instructions: Vec<String> = term_assignment.to_assembly_instructions(target_register, variable_history, labels)?;
+
+            // Apply the operator to it in place
+            match (operator, term_assignment.evaluate_type(variable_history)) {
+                (UnaryOperator::NEG, DataType::FLOAT) => { returned_instructions.append(&mut vec![
+                    format!("  movq xmm0, {}", target_register),
+                    format!("  mov {}, 0x8000000000000000", target_register),
+                    format!("  movq xmm1, {}", target_register),
+                    format!("  xorpd xmm0, xmm1"),
+                    format!("  movq {}, xmm0", target_register),
+                ]);}
+                (UnaryOperator::NEG, _) => { returned_instructions.append(&mut vec![
+                    format!("  neg {}", target_register),
+                ]);}
+                (UnaryOperator::BNOT, DataType::FLOAT) => {
+                    return Err(AssemblerError::IntegerOnlyOperator { operator: SyntaxElements::init().math_symbols["bitwise not"].clone(), span: *span })
+                }
+                (UnaryOperator::BNOT, _) => { returned_instructions.append(&mut vec![
+                    format!("  not {}", target_register),
+                ]);}
+                (UnaryOperator::NOT, _) => { returned_instructions.append(&mut vec![
+                    format!("  xor {}, 1", target_register),
+                ]);}
+            }
+
+            return Ok(returned_instructions)
+        }
+
+        Self::CAST(term_assignment, data_type, _) => {
+            // Convert the term into rax
+            let mut returned_instructions: Vec<String> = term_assignment.to_assembly_instructions("rax", variable_history, labels)?;
+
+            // Convert it to its new type (`tof` values are already 1 or 0 as an `int`)
+            match (term_assignment.evaluate_type(variable_history), data_type) {
+                (DataType::INTEGER, DataType::FLOAT) => { returned_instructions.append(&mut vec![
+                    format!("  cvtsi2sd xmm0, rax"),
+                    format!("  movq rax, xmm0"),
+                ]);}
+                (DataType::FLOAT, DataType::INTEGER) => { returned_instructions.append(&mut vec![
+                    format!("  movq xmm0, rax"),
+                    format!("  cvttsd2si rax, xmm0"),
+                ]);}
+                _ => {}
+            }
+
+            // Place the result into the target register
+            if target_register != "rax" { returned_instructions.append(&mut vec![
+                format!("  mov {}, rax", target_register)
+            ]);}
+
+            return Ok(returned_instructions)
+        }
+
+        Self::INTEGER(returned_number, _) => {
             let mut returned_instructions: Vec<String> = Vec::new();
 
             returned_instructions.append(&mut vec![
@@ -140,7 +333,7 @@ impl AssignmentToAssembly for Assignment {
             return Ok(returned_instructions)
         }
 
-        Self::FLOAT(returned_number) => {
+        Self::FLOAT(returned_number, _) => {
             let mut returned_instructions: Vec<String> = Vec::new();
 
             returned_instructions.append(&mut vec![
@@ -150,7 +343,7 @@ impl AssignmentToAssembly for Assignment {
             return Ok(returned_instructions)
         }
 
-        Self::FUNC(function_name, _, function_args) => {
+        Self::FUNC(function_name, return_type, function_args, _) => {
             let mut returned_instructions: Vec<String> = Vec::new();
 
             // Push rax to stack if it's not the target register
@@ -158,16 +351,61 @@ impl AssignmentToAssembly for Assignment {
                 format!("  push rax")
             ]);}
 
-            // Write the function arguments
-            for (argument_index, argument) in function_args.iter().enumerate() { returned_instructions.append(&mut vec![
-                format!("  mov {}, {}", FUNCTION_ARGUMENT_REGISTERS[argument_index], argument.to_assembly_value().unwrap()),
-            ])};
+            // Evaluate every argument onto the stack first, as evaluating an argument (another
+            // call, a division...) can overwrite the registers of the arguments before it
+            for argument in function_args.iter() {
+                returned_instructions.append(&mut argument.to_assembly_instructions("rax", variable_history, labels)?);
+                returned_instructions.append(&mut vec![
+                    format!("  push rax"),
+                ]);
+            }
+
+            let argument_types: Vec<DataType> = function_args.iter().map(|x| x.evaluate_type(variable_history)).collect();
+            let argument_locations = locate_arguments(&argument_types);
+            let stack_argument_count = count_stack_arguments(&argument_locations);
+
+            // Keep track of where the evaluated arguments are, then line the stack up so it's
+            // 16 byte aligned once every stack argument is pushed
+            returned_instructions.append(&mut vec![
+                format!("  mov {}, rsp", CALL_FRAME_REGISTER),
+                format!("  and rsp, -16"),
+            ]);
+            if stack_argument_count % 2 == 1 { returned_instructions.append(&mut vec![
+                format!("  sub rsp, 8"),
+            ]);}
+
+            // The last argument evaluated is the closest to the top of the stack
+            let evaluated_argument_offset = |argument_index: usize| (function_args.len() - 1 - argument_index) * 8;
+
+            // Push the stack arguments, last argument first
+            for (argument_index, argument_location) in argument_locations.iter().enumerate().rev() { if let ArgumentLocation::Stack(_) = argument_location {
+                returned_instructions.append(&mut vec![
+                    format!("  push QWORD [{}+{}]", CALL_FRAME_REGISTER, evaluated_argument_offset(argument_index)),
+                ]);
+            }}
+
+            // Then move the rest into their registers
+            for (argument_index, argument_location) in argument_locations.iter().enumerate() { match argument_location {
+                ArgumentLocation::IntegerRegister(register) => { returned_instructions.append(&mut vec![
+                    format!("  mov {}, QWORD [{}+{}]", register, CALL_FRAME_REGISTER, evaluated_argument_offset(argument_index)),
+                ]);}
+                ArgumentLocation::FloatRegister(register) => { returned_instructions.append(&mut vec![
+                    format!("  movsd {}, QWORD [{}+{}]", register, CALL_FRAME_REGISTER, evaluated_argument_offset(argument_index)),
+                ]);}
+                ArgumentLocation::Stack(_) => {}
+            }}
 
-            // Call the function
+            // Call the function, then drop its stack arguments and the evaluated arguments
             returned_instructions.append(&mut vec![
                 format!("  call {}", function_name),
+                format!("  lea rsp, [{}+{}]", CALL_FRAME_REGISTER, function_args.len() * 8),
             ]);
 
+            // `flt` values are returned in xmm0
+            if return_type.is_float() { returned_instructions.append(&mut vec![
+                format!("  movq rax, xmm0"),
+            ]);}
+
             // Place the result of the function into the associated register
             // If not rax, pop the original value of rax back
             if target_register != "rax" { returned_instructions.append(&mut vec![
@@ -178,17 +416,17 @@ impl AssignmentToAssembly for Assignment {
             return Ok(returned_instructions);
         }
 
-        Self::VAR(variable_index) => {
+        Self::VAR(variable_index, _) => {
             let mut returned_instructions: Vec<String> = Vec::new();
 
             returned_instructions.append(&mut vec![
-                format!("  mov {}, QWORD [rbp-{}]", target_register, variable_history.step * (variable_index+1)),
+                format!("  mov {}, QWORD [rbp-{}]", target_register, variable_history.stack_offset(*variable_index)),
             ]);
 
             return Ok(returned_instructions)
         }
 
-        Self::BOOL(boolean_val) => {
+        Self::BOOL(boolean_val, _) => {
             let mut returned_instructions: Vec<String> = Vec::new();
 
             returned_instructions.append(&mut vec![
@@ -199,9 +437,103 @@ impl AssignmentToAssembly for Assignment {
         }
     }}
 
+    fn to_condition_instructions(&self, jump_if: bool, jump_label: &str, variable_history: &VariableHistory, labels: &mut LabelAllocator) -> Result<Vec<String>, AssemblerError> { match self {
+        Self::CMP(first_term_assignment, operator, second_term_assignment, _) if is_float_comparison(first_term_assignment, second_term_assignment, variable_history) => {
+            let mut returned_instructions: Vec<String> = float_comparison_instructions(first_term_assignment, operator, second_term_assignment, variable_history, labels)?;
+
+            // Jump using the unsigned flags, where every comparison with NaN is false (other
+            // than not equal to)
+            match (operator, jump_if) {
+                // Only jump if the terms are equal and neither is NaN
+                (ComparisonOperator::EQ, true) | (ComparisonOperator::NEQ, false) => {
+                    let skip_label = labels.allocate("fcmp_skip");
+
+                    returned_instructions.append(&mut vec![
+                        format!("  jp {}", skip_label),
+                        format!("  je {}", jump_label),
+                        format!("{}:", skip_label),
+                    ]);
+                }
+                (ComparisonOperator::EQ, false) | (ComparisonOperator::NEQ, true) => { returned_instructions.append(&mut vec![
+                    format!("  jne {}", jump_label),
+                    format!("  jp {}", jump_label),
+                ]);}
+                (ComparisonOperator::GT | ComparisonOperator::LT, true) => { returned_instructions.append(&mut vec![
+                    format!("  ja {}", jump_label),
+                ]);}
+                (ComparisonOperator::GT | ComparisonOperator::LT, false) => { returned_instructions.append(&mut vec![
+                    format!("  jbe {}", jump_label),
+                ]);}
+                (ComparisonOperator::GEQ | ComparisonOperator::LEQ, true) => { returned_instructions.append(&mut vec![
+                    format!("  jae {}", jump_label),
+                ]);}
+                (ComparisonOperator::GEQ | ComparisonOperator::LEQ, false) => { returned_instructions.append(&mut vec![
+                    format!("  jb {}", jump_label),
+                ]);}
+            }
+
+            return Ok(returned_instructions)
+        }
+
+        Self::CMP(first_term_assignment, operator, second_term_assignment, _) => {
+            // Compare the first and second terms
+            // The first term is kept on the stack while the second is evaluated
+            let mut returned_instructions: Vec<String> = [
+                first_term_assignment.to_assembly_instructions("rax", variable_history, labels)?,
+                vec![format!("  push rax")],
+                second_term_assignment.to_assembly_instructions("rsi", variable_history, labels)?,
+                vec![format!("  pop rdi")],
+                vec![format!("  cmp rdi, rsi")],
+            ].concat();
+
+            // Jump if the comparison matches `jump_if`
+            let operator = if jump_if { operator.clone() } else { operator.negation() };
+            returned_instructions.append(&mut vec![
+                format!("  {} {}", jump_instruction(&operator), jump_label),
+            ]);
+
+            return Ok(returned_instructions)
+        }
+
+        Self::LOGIC(first_term_assignment, operator, second_term_assignment, _) => { match (operator, jump_if) {
+            // The first term alone can decide to jump, otherwise the second term decides
+            (LogicalOperator::AND, false) | (LogicalOperator::OR, true) => {
+                return Ok([
+                    first_term_assignment.to_condition_instructions(jump_if, jump_label, variable_history, labels)?,
+                    second_term_assignment.to_condition_instructions(jump_if, jump_label, variable_history, labels)?,
+                ].concat())
+            }
+            // The first term alone can decide not to jump, so it skips over the second term
+            (LogicalOperator::AND, true) | (LogicalOperator::OR, false) => {
+                let skip_label = labels.allocate("logic_skip");
+
+                return Ok([
+                    first_term_assignment.to_condition_instructions(!jump_if, &skip_label, variable_history, labels)?,
+                    second_term_assignment.to_condition_instructions(jump_if, jump_label, variable_history, labels)?,
+                    vec![format!("{}:", skip_label)],
+                ].concat())
+            }
+        }}
+
+        Self::UNARY(UnaryOperator::NOT, term_assignment, _) => {
+            return term_assignment.to_condition_instructions(!jump_if, jump_label, variable_history, labels)
+        }
+
+        // Any other tof value is tested against 0
+        _ => {
+            let mut returned_instructions: Vec<String> = self.to_assembly_instructions("rax", variable_history, labels)?;
+            returned_instructions.append(&mut vec![
+                format!("  test rax, rax"),
+                format!("  {} {}", if jump_if { "jnz" } else { "jz" }, jump_label),
+            ]);
+
+            return Ok(returned_instructions)
+        }
+    }}
+
     fn to_assembly_value(&self) -> Result<String, AssemblerError> { match self {
-        Assignment::INTEGER(returned_num) => { return Ok(returned_num.to_assembly_value()) }
-        Assignment::FLOAT(returned_num)   => { return Ok(returned_num.to_assembly_value()) }
-                                        _ => { return Err(AssemblerError::IncorrectAssignmentAttemptedToConvert) }
+        Assignment::INTEGER(returned_num, _) => { return Ok(returned_num.to_assembly_value()) }
+        Assignment::FLOAT(returned_num, _) => { return Ok(returned_num.to_assembly_value()) }
+                                        _ => { return Err(AssemblerError::IncorrectAssignmentAttemptedToConvert { span: self.span() }) }
     }}
 }
@@ -35,6 +35,7 @@ pub struct SyntaxElements {
     pub assignment_symbols: HashMap<String, String>,
     pub comparision_symbols: HashMap<String, String>,
     pub comparision_names: HashMap<String, String>,
+    pub operator_names: HashMap<String, String>,
 
 } impl SyntaxElements {
     /// Initialize the SyntaxElements
@@ -74,6 +75,12 @@ pub struct SyntaxElements {
             "conditional statement"      => "cmpr",
             "else conditional statement" => "otherwise",
             "conditional loop"           => "repeat",
+            "terminating loop"           => "loop",
+            "loop start"                 => "from",
+            "loop end"                   => "to",
+            "loop step"                  => "step",
+            "break"                      => "break",
+            "continue"                   => "continue",
         ].to_string_hashmap(),
 
         math_symbols: hashmap![
@@ -81,6 +88,13 @@ pub struct SyntaxElements {
             "subtraction"    => "-",
             "multiplication" => "*",
             "division"       => "/",
+            "modulo"         => "%",
+            "bitwise and"    => "&",
+            "bitwise or"     => "|",
+            "bitwise xor"    => "^",
+            "bitwise not"    => "~",
+            "shift left"     => "<<",
+            "shift right"    => ">>",
         ].to_string_hashmap(),
 
         assignment_symbols: hashmap![
@@ -101,6 +115,8 @@ pub struct SyntaxElements {
             "begin enclosure"             => "[",
             "end enclosure"               => "]",
             "return this"                 => "->",
+            "begin group"                 => "(",
+            "end group"                   => ")",
         ].to_string_hashmap(), 
 
         comparision_symbols: hashmap![
@@ -115,7 +131,14 @@ pub struct SyntaxElements {
         comparision_names: hashmap![
             "true"  => "true",
             "false" => "false",
-        ].to_string_hashmap()
+        ].to_string_hashmap(),
+
+        operator_names: hashmap![
+            "not"  => "not",
+            "and"  => "and",
+            "or"   => "or",
+            "cast" => "as",
+        ].to_string_hashmap(),
     }}                         
 
     /// Gets every value held in every HashMap as a Vec<String>
@@ -142,6 +165,10 @@ pub struct SyntaxElements {
             result.push(element_name);
         }
 
+        for (_, element_name) in self.operator_names.clone() {
+            result.push(element_name);
+        }
+
         return result;
     }
 
@@ -0,0 +1,235 @@
+use colored::{Color, Colorize};
+
+use crate::optimizer::span::Span;
+
+
+/// A file given to the compiler, kept around so diagnostics can quote it
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    pub name: String,
+    pub content: String,
+}
+
+
+/// Holds every file given to the compiler, where the index of a file is its `file_id`
+pub struct SourceMap {
+    files: Vec<SourceFile>,
+
+} impl SourceMap {
+    pub fn init() -> Self { Self {
+        files: Vec::new(),
+    }}
+
+    /// Adds a file to this `SourceMap` and returns the `file_id` spans into it should use
+    pub fn add_file(&mut self, name: &str, content: &str) -> usize {
+        self.files.push(SourceFile {
+            name: name.to_string(),
+            content: content.to_string(),
+        });
+
+        return self.files.len() - 1
+    }
+
+    /// Gets a file by its `file_id`
+    ///
+    /// Returns None if no file was added with this id
+    pub fn get_file(&self, file_id: usize) -> Option<&SourceFile> {
+        return self.files.get(file_id)
+    }
+}
+
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+} impl Severity {
+    fn name(&self) -> &'static str { match self {
+        Self::Error   => { return "error"   }
+        Self::Warning => { return "warning" }
+    }}
+
+    fn color(&self) -> Color { match self {
+        Self::Error   => { return Color::Red    }
+        Self::Warning => { return Color::Yellow }
+    }}
+}
+
+
+/// A span of source text to underline, along with what to write under it
+///
+/// Primary labels point at the cause of the diagnostic and are underlined with `^`. Secondary
+/// labels give extra context and are underlined with `-`
+#[derive(Debug, Clone)]
+pub struct Label {
+    pub span: Span,
+    pub message: String,
+    pub is_primary: bool,
+}
+
+
+/// An error or warning to report to the user
+///
+/// Diagnostics are built up from a message, then given labels, notes and suggestions
+///
+/// # Examples
+///
+/// ```rust
+/// let diagnostic = Diagnostic::error("use of undeclared variable `y`")
+///     .with_code("E0104")
+///     .with_label(span_of_y, "not found in this function")
+///     .with_help("declare it first with `decl y:=[int] = 0`");
+///
+/// eprintln!("{}", diagnostic.render(&source_map));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub code: Option<String>,
+    pub message: String,
+    pub labels: Vec<Label>,
+    pub notes: Vec<String>,
+    pub helps: Vec<String>,
+
+} impl Diagnostic {
+    pub fn new(severity: Severity, message: &str) -> Self { Self {
+        severity,
+        code: None,
+        message: message.to_string(),
+        labels: Vec::new(),
+        notes: Vec::new(),
+        helps: Vec::new(),
+    }}
+
+    pub fn error(message: &str) -> Self {
+        return Self::new(Severity::Error, message)
+    }
+
+    pub fn warning(message: &str) -> Self {
+        return Self::new(Severity::Warning, message)
+    }
+
+    pub fn with_code(mut self, code: &str) -> Self {
+        self.code = Some(code.to_string());
+        return self
+    }
+
+    /// Underlines `span` with `^` and writes `message` under it
+    pub fn with_label(mut self, span: Span, message: &str) -> Self {
+        self.labels.push(Label { span, message: message.to_string(), is_primary: true });
+        return self
+    }
+
+    /// Underlines `span` with `-` and writes `message` under it
+    pub fn with_secondary_label(mut self, span: Span, message: &str) -> Self {
+        self.labels.push(Label { span, message: message.to_string(), is_primary: false });
+        return self
+    }
+
+    pub fn with_note(mut self, note: &str) -> Self {
+        self.notes.push(note.to_string());
+        return self
+    }
+
+    pub fn with_help(mut self, help: &str) -> Self {
+        self.helps.push(help.to_string());
+        return self
+    }
+
+    pub fn is_error(&self) -> bool {
+        return self.severity == Severity::Error
+    }
+
+    /// Renders this diagnostic the way rustc does, quoting every labelled line of source
+    ///
+    /// ```txt
+    /// error[E0104]: use of undeclared variable `y`
+    ///  --> examples/main.uml:6:21
+    ///   |
+    /// 6 |     decl x:=[int] = y
+    ///   |                     ^ not found in this function
+    ///   |
+    ///   = help: declare it first with `decl y:=[int] = 0`
+    /// ```
+    pub fn render(&self, source_map: &SourceMap) -> String {
+        let severity_color = self.severity.color();
+
+        // Header
+        let header = match &self.code {
+            Some(code) => { format!("{}[{}]", self.severity.name(), code) }
+            None       => { self.severity.name().to_string() }
+        };
+        let mut result = format!("{}{}\n", header.color(severity_color).bold(), format!(": {}", self.message).bold());
+
+        // Only labels pointing into a known file can be quoted, and a span on line 0 (such as
+        // `Span::default()`) has no location to quote
+        let mut labels: Vec<&Label> = self.labels.iter()
+            .filter(|x| x.span.line > 0 && source_map.get_file(x.span.file_id).is_some())
+            .collect();
+        labels.sort_by_key(|x| (x.span.file_id, x.span.line, x.span.column));
+
+        // The gutter has to fit the largest line number quoted
+        let gutter_width = labels.iter()
+            .map(|x| x.span.line.to_string().len())
+            .max()
+            .unwrap_or(0);
+        let gutter = " ".repeat(gutter_width);
+        let pipe = "|".color(Color::Blue).bold();
+
+        // Point at the primary label first (or the first label if there is none)
+        let mut current_file: Option<usize> = None;
+        let location_label = labels.iter().find(|x| x.is_primary).or(labels.first()).copied();
+        for label in labels.iter() {
+            let file = source_map.get_file(label.span.file_id).unwrap();
+
+            // Start a new snippet every time the file changes
+            if current_file != Some(label.span.file_id) {
+                let arrow = if current_file.is_none() { "-->" } else { ":::" };
+                let location_span = match location_label {
+                    Some(location_label) if current_file.is_none() && location_label.span.file_id == label.span.file_id => { location_label.span }
+                    _ => { label.span }
+                };
+
+                result += &format!("{}{} {}:{}:{}\n", gutter, arrow.color(Color::Blue).bold(), file.name, location_span.line, location_span.column);
+                result += &format!("{} {}\n", gutter, pipe);
+                current_file = Some(label.span.file_id);
+            }
+
+            // Quote the line the label starts on
+            let line_content: String = file.content.lines()
+                .nth(label.span.line - 1)
+                .unwrap_or("")
+                .replace('\t', " ");
+            let line_number = format!("{:>width$}", label.span.line, width = gutter_width);
+            result += &format!("{} {} {}\n", line_number.color(Color::Blue).bold(), pipe, line_content);
+
+            // Underline the label, stopping at the end of the line if the span runs past it
+            let span_text = file.content.get(label.span.start..label.span.end).unwrap_or("");
+            let span_width = span_text.lines().next().unwrap_or("").chars().count().max(1);
+            let underline_character = if label.is_primary { "^" } else { "-" };
+            let underline_color = if label.is_primary { severity_color } else { Color::Blue };
+            let underline = format!("{} {}", underline_character.repeat(span_width), label.message);
+
+            result += &format!("{} {} {}{}\n", gutter, pipe, " ".repeat(label.span.column.saturating_sub(1)), underline.trim_end().color(underline_color).bold());
+        }
+
+        // Notes and suggestions
+        if !labels.is_empty() && (!self.notes.is_empty() || !self.helps.is_empty()) {
+            result += &format!("{} {}\n", gutter, pipe);
+        }
+        for note in self.notes.iter() {
+            result += &format!("{} {} {}: {}\n", gutter, "=".color(Color::Blue).bold(), "note".bold(), note);
+        }
+        for help in self.helps.iter() {
+            result += &format!("{} {} {}: {}\n", gutter, "=".color(Color::Blue).bold(), "help".bold(), help);
+        }
+
+        return result
+    }
+}
+
+
+/// Implemented by every error type that can be reported to the user as a `Diagnostic`
+pub trait ToDiagnostic {
+    fn to_diagnostic(&self) -> Diagnostic;
+}
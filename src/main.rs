@@ -1,4 +1,5 @@
 use std::fs;
+use std::panic;
 use std::process;
 use std::env;
 
@@ -6,12 +7,20 @@ use std::env;
 mod tokenizer;
     use tokenizer::Tokenizer;
 
+#[allow(dead_code)]
+mod typeck;
+    use typeck::TypeChecker;
+
 mod assembler;
     use assembler::Assembler;
 
 mod optimizer;
     use optimizer::Optimizer;
 
+#[allow(dead_code)]
+mod diagnostics;
+    use diagnostics::{Diagnostic, SourceMap, ToDiagnostic};
+
 #[allow(dead_code)]
 mod type_traits;
 
@@ -20,57 +29,160 @@ mod data;
 
 
 fn main() {
+    // Report panics as internal compiler errors rather than a backtrace
+    panic::set_hook(Box::new(|panic_info| {
+        // `panic!` carries a `&str` when it's given a plain message and a `String` when it formats one
+        let payload = panic_info.payload();
+        let message = payload.downcast_ref::<&str>().copied()
+            .or_else(|| payload.downcast_ref::<String>().map(|x| x.as_str()))
+            .unwrap_or("the compiler panicked");
+        let mut diagnostic = Diagnostic::error(&format!("internal compiler error: {message}"))
+            .with_note("this is a bug in the compiler, not in your program");
+        if let Some(location) = panic_info.location() {
+            diagnostic = diagnostic.with_note(&format!("the compiler panicked at {}:{}:{}", location.file(), location.line(), location.column()));
+        }
+
+        eprint!("{}", diagnostic.render(&SourceMap::init()));
+    }));
+
+    let mut source_map = SourceMap::init();
+
     let args: Vec<String> = env::args().collect();
-    let file_path = args[1].clone();
+    let Some(file_path) = args.get(1).cloned() else {
+        exit_with_diagnostic(&source_map, Diagnostic::error("no input file was given")
+            .with_code("E0001")
+            .with_help("pass the file to compile, e.g. `coding_language examples/main.uml`"))
+    };
 
     // Read from file and flatten it
-    let file_content: String = fs::read_to_string(file_path)
-        .expect("Failed to read file");
+    let file_content: String = match fs::read_to_string(&file_path) {
+        Ok(file_content) => { file_content }
+        Err(error)       => { exit_with_diagnostic(&source_map, Diagnostic::error(&format!("could not read `{file_path}`: {error}"))
+            .with_code("E0002")) }
+    };
+    let file_id = source_map.add_file(&file_path, &file_content);
     let mut optimizer = Optimizer::init();
-    optimizer.generate_optimized_content(&file_content);
+    optimizer.generate_optimized_content(file_id, &file_content);
 
     // Tokenize the flattened content
     let mut tokenizer = Tokenizer::init();
-    tokenizer.create_token_tree(&optimizer.content);
+    if let Err(errors) = tokenizer.create_token_tree(&optimizer.content) {
+        exit_with_diagnostics(&source_map, errors.iter().map(|x| x.to_diagnostic()).collect());
+    }
     println!("Token Tree: {:?}", tokenizer.token_tree);
 
+    // Check the generated token tree uses every value where its type is expected
+    let mut type_checker = TypeChecker::init();
+    if let Err(errors) = type_checker.check_token_tree(&tokenizer.token_tree) {
+        exit_with_diagnostics(&source_map, errors.iter().map(|x| x.to_diagnostic()).collect());
+    }
+
     // Essemble the generated token tree
     let mut assembler = Assembler::init();
-    assembler.generate_instructions(&tokenizer.token_tree).unwrap();
+    if let Err(error) = assembler.generate_instructions(&tokenizer.token_tree) {
+        exit_with_diagnostic(&source_map, error.to_diagnostic());
+    }
     
     // Write the assembled content to a file
     let program_content = assembler.instructions.join("\n");
-    fs::write("./build/output.asm", program_content)
-        .expect("Failed to write a.asm");
+    if let Err(error) = fs::create_dir_all("./build").and_then(|_| fs::write("./build/output.asm", program_content)) {
+        exit_with_diagnostic(&source_map, Diagnostic::error(&format!("could not write `build/output.asm`: {error}"))
+            .with_code("E0003"));
+    }
 
     // Assemble (using nasm/ld) the final assembly file
     // Note this is compiled in debug mode
-    process::Command::new("nasm")
-        .args(["-f", "elf64"])
-        .args(["-g"])
-        .args(["-F", "dwarf"])
-        .args(["build/output.asm"])
-        .args(["-o", "a.o"])
-        .status().unwrap();
-    process::Command::new("ld")
-        .args(["a.o"])
-        .args(["-o", "build/a.out"])
-        .status().unwrap();
+    run_external_tool(&source_map, "nasm", &["-f", "elf64", "-g", "-F", "dwarf", "build/output.asm", "-o", "a.o"]);
+    run_external_tool(&source_map, "ld", &["a.o", "-o", "build/a.out"]);
 
     // Clean up extra files
-    fs::remove_file("a.o")
-        .expect("Failed to remove a.o");
+    if let Err(error) = fs::remove_file("a.o") {
+        exit_with_diagnostic(&source_map, Diagnostic::error(&format!("could not remove `a.o`: {error}"))
+            .with_code("E0003"));
+    }
     //fs::remove_file("output.asm")
     //    .expect("Failed to remove output.asm");
 }
 
+/// Renders `diagnostic` to stderr and exits with a failing status
+fn exit_with_diagnostic(source_map: &SourceMap, diagnostic: Diagnostic) -> ! {
+    exit_with_diagnostics(source_map, vec![diagnostic]);
+}
+
+/// Renders every diagnostic to stderr (followed by how many errors there were) and exits with
+/// a failing status
+fn exit_with_diagnostics(source_map: &SourceMap, diagnostics: Vec<Diagnostic>) -> ! {
+    for diagnostic in diagnostics.iter() {
+        eprintln!("{}", diagnostic.render(source_map));
+    }
+
+    let error_count = diagnostics.iter().filter(|x| x.is_error()).count();
+    if error_count > 1 {
+        eprint!("{}", Diagnostic::error(&format!("aborting due to {error_count} previous errors")).render(source_map));
+    }
+
+    process::exit(1);
+}
+
+/// Runs an external tool (nasm, ld) and exits with a diagnostic if it could not be run or failed
+fn run_external_tool(source_map: &SourceMap, program: &str, args: &[&str]) {
+    match process::Command::new(program).args(args).status() {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            exit_with_diagnostic(source_map, Diagnostic::error(&format!("`{program}` failed ({status})"))
+                .with_code("E0005")
+                .with_note("the generated assembly was kept at `build/output.asm`"));
+        }
+        Err(error) => {
+            exit_with_diagnostic(source_map, Diagnostic::error(&format!("could not run `{program}`: {error}"))
+                .with_code("E0004")
+                .with_help(&format!("ensure `{program}` is installed and on your PATH")));
+        }
+    }
+}
+
 #[cfg(test)]
 mod testing {
     use std::fs;
 
     use crate::optimizer::Optimizer;
-    use crate::tokenizer::Tokenizer;
+    use crate::optimizer::span::Span;
+    use crate::diagnostics::{Diagnostic, SourceMap};
+    use crate::tokenizer::{Token, Tokenizer};
+    use crate::tokenizer::enumerators::Assignment;
+    use crate::typeck::TypeChecker;
+    use crate::assembler::calling_convention::{ArgumentLocation, locate_arguments};
+    use crate::assembler::Assembler;
+    use crate::assembler::labels::LabelAllocator;
+    use crate::tokenizer::error::TokenizerError;
 
+    /// Tokenizes a file, giving back its token tree or every error found in it
+    fn tokenize_all(file_content: &str) -> Result<Vec<Token>, Vec<TokenizerError>> {
+        let mut optimizer = Optimizer::init();
+        optimizer.generate_optimized_content(0, file_content);
+
+        let mut tokenizer = Tokenizer::init();
+        return tokenizer.create_token_tree(&optimizer.content).map(|_| tokenizer.token_tree)
+    }
+
+    /// Tokenizes a file, giving back its token tree or the first error found in it
+    fn tokenize_file(file_content: &str) -> Result<Vec<Token>, TokenizerError> {
+        return tokenize_all(file_content).map_err(|mut x| x.remove(0))
+    }
+
+    /// Tokenizes and assembles a file, giving back its instructions without their indentation
+    fn assemble_file(file_content: &str) -> Vec<String> {
+        let token_tree = tokenize_file(file_content).unwrap();
+
+        let mut assembler = Assembler::init();
+        assembler.generate_instructions(&token_tree).unwrap();
+        return assembler.instructions.iter().map(|x| x.trim().to_string()).collect()
+    }
+
+    /// Whether every instruction of `expected` is found in `instructions`, one right after another
+    fn contains_in_order(instructions: &[String], expected: &[&str]) -> bool {
+        return instructions.windows(expected.len()).any(|x| x.iter().zip(expected).all(|(a, b)| a == b))
+    }
 
     #[test]
     fn test() {
@@ -83,35 +195,476 @@ mod testing {
         // Read from file and flatten it
         let file_content: String = fs::read_to_string("./examples/main.uml")
             .expect("Failed to read file");
-        let mut optimizer = Optimizer::init();
-        optimizer.generate_optimized_content(&file_content);
 
         // Tokenize the flattened content
+        let token_tree = tokenize_file(&file_content).unwrap();
+        println!("Token Tree: {:?}", token_tree);
+    }
+
+    #[test]
+    fn spans() {
+        let file_content = "subroutine main::[] -> int :\n    decl x:=[int] = 12\n    expose x\n;\n";
+        let mut optimizer = Optimizer::init();
+        optimizer.generate_optimized_content(3, file_content);
+
+        // Every word keeps the file, byte offset, line and column it came from
+        let twelve = optimizer.content.iter().find(|x| **x == *"12").unwrap();
+        assert_eq!(twelve.span, Span::new(3, 49, 51, 2, 21));
+
+        // Every token keeps the span of the statement that produced it
         let mut tokenizer = Tokenizer::init();
-        tokenizer.create_token_tree(&optimizer.content);
-        println!("Token Tree: {:?}", tokenizer.token_tree);
+        tokenizer.create_token_tree(&optimizer.content).unwrap();
+        let Token::Function(function) = &tokenizer.token_tree[0] else { panic!("expected a function") };
+        assert_eq!(function.span, Span::new(3, 0, 66, 1, 1));
+        assert_eq!(function.functionaliy[0].span(), Span::new(3, 33, 51, 2, 5));
+        assert_eq!(function.functionaliy[1].span(), Span::new(3, 56, 64, 3, 5));
     }
 
-    /*
     #[test]
     fn assemble() {
         // Read from file and flatten it
         let file_content: String = fs::read_to_string("./examples/main.uml")
             .expect("Failed to read file");
-        let mut optimizer = Optimizer::init();
-        optimizer.generate_optimized_content(&file_content);
 
-        // Tokenize the flattened content
-        let mut tokenizer = Tokenizer::init();
-        tokenizer.create_token_tree(&optimizer.content);
+        // Every subroutine is assembled behind the program's entry point
+        let instructions = assemble_file(&file_content);
+        assert!(contains_in_order(&instructions, &["global _start", "_start:", "call main"]));
+        assert!(contains_in_order(&instructions, &["global main", "main:", "push rbp", "mov rbp, rsp", "push rbx"]));
+    }
 
-        // Essemble the generated token tree
-        let mut assembler = Assembler::init();
-        assembler.generate_instructions(&tokenizer.token_tree).unwrap();
+    #[test]
+    fn division() {
+        let instructions = assemble_file(&[
+            "subroutine main::[] -> int :",
+            "    decl q:=[int] = 7 / 2",
+            "    decl r:=[int] = 7 % 2",
+            "    expose q + r",
+            ";",
+        ].join("\n"));
+
+        // `/` and `%` both call int_divide, which traps on a division by zero, and `%` takes the
+        // remainder it leaves in rdx
+        assert!(contains_in_order(&instructions, &["call int_divide", "mov rdi, rax", "mov rax, rdi", "mov QWORD [rbp-16], rax"]));
+        assert!(contains_in_order(&instructions, &["call int_divide", "mov rax, rdx"]));
+        assert!(contains_in_order(&instructions, &["int_divide:", "test rdi, rdi", "jz division_by_zero_trap"]));
+        assert!(instructions.iter().any(|x| x == "division_by_zero_trap:"));
+
+        // Errors found while assembling point at the equation they came from
+        let token_tree = tokenize_file("subroutine main::[] -> int :\n    decl x:=[flt] = 1.5 % 2.0\n    expose 0\n;\n").unwrap();
+        let error = Assembler::init().generate_instructions(&token_tree).unwrap_err();
+        assert_eq!((error.code(), error.span()), ("E0207", Some(Span::new(0, 49, 58, 2, 21))));
+    }
+
+    #[test]
+    fn float_comparisons() {
+        let instructions = assemble_file(&[
+            "subroutine main::[] -> int :",
+            "    decl x:=[flt] = 1.5",
+            "    decl e:=[tof] = x == 2.0",
+            "    decl l:=[tof] = x < 2.0",
+            "    decl n:=[int] = 0",
+            "    cmpr :&[x, n] :",
+            "        [x != 2.0] :",
+            "            chng n = 1",
+            "        ;",
+            "    ;",
+            "    expose n",
+            ";",
+        ].join("\n"));
+
+        // A NaN is never equal to anything, which `ucomisd` reports through the parity flag
+        assert!(contains_in_order(&instructions, &["ucomisd xmm0, xmm1", "sete al", "setnp cl", "and al, cl"]));
+        assert!(contains_in_order(&instructions, &["ucomisd xmm1, xmm0", "seta al"]));
+
+        // A branch on `!=` is also taken when the operands are unordered
+        assert!(contains_in_order(&instructions, &["ucomisd xmm0, xmm1", "jne cmpr_branch_0", "jp cmpr_branch_0"]));
+    }
+
+    #[test]
+    fn calls() {
+        let instructions = assemble_file(&[
+            "subroutine add::[int a, int b] -> int :",
+            "    expose a + b",
+            ";",
+            "subroutine main::[] -> int :",
+            "    decl q:=[int] = 2",
+            "    expose add::[q * 2, add::[q, 1]]",
+            ";",
+        ].join("\n"));
+
+        // Every argument is evaluated and pushed before any of them are moved into their
+        // registers, so the inner call can't overwrite the first argument
+        assert!(contains_in_order(&instructions, &[
+            "imul rax, rdi",
+            "push rax",
+            "mov rax, QWORD [rbp-16]",
+            "push rax",
+            "mov rax, 1",
+            "push rax",
+            "mov rbx, rsp",
+            "and rsp, -16",
+            "mov rdi, QWORD [rbx+8]",
+            "mov rsi, QWORD [rbx+0]",
+            "call add",
+            "lea rsp, [rbx+16]",
+            "push rax",
+            "mov rbx, rsp",
+            "and rsp, -16",
+            "mov rdi, QWORD [rbx+8]",
+            "mov rsi, QWORD [rbx+0]",
+            "call add",
+            "lea rsp, [rbx+16]",
+        ]));
+    }
+
+    #[test]
+    fn stack_arguments() {
+        let instructions = assemble_file(&[
+            "subroutine seven::[int a, int b, int c, int d, int e, int f, int g] -> int :",
+            "    expose a + g",
+            ";",
+            "subroutine main::[] -> int :",
+            "    expose seven::[1, 2, 3, 4, 5, 6, 7]",
+            ";",
+        ].join("\n"));
+
+        // The 7th `int` argument is pushed after padding the stack, so it's aligned at the call
+        assert!(contains_in_order(&instructions, &["mov rbx, rsp", "and rsp, -16", "sub rsp, 8", "push QWORD [rbx+0]", "mov rdi, QWORD [rbx+48]"]));
+        assert!(contains_in_order(&instructions, &["call seven", "lea rsp, [rbx+56]"]));
+
+        // and read back from right above the return address
+        assert!(contains_in_order(&instructions, &["mov QWORD [rbp-56], r9", "mov rax, QWORD [rbp+16]", "mov QWORD [rbp-64], rax"]));
+        assert!(contains_in_order(&instructions, &["lea rsp, [rbp-8]", "pop rbx", "pop rbp", "ret"]));
+    }
 
-        for instruction in assembler.instructions {
-            println!("{instruction}");
+    #[test]
+    fn stack_frames() {
+        let instructions = assemble_file(&[
+            "subroutine main::[] -> int :",
+            "    decl a:=[int] = 1",
+            "    cmpr :&[a] :",
+            "        [a > 0] :",
+            "            decl x:=[int] = 2",
+            "        ;",
+            "    ;",
+            "    decl y:=[int] = a",
+            "    expose y",
+            ";",
+            "subroutine zero::[] -> int :",
+            "    expose 0",
+            ";",
+        ].join("\n"));
+
+        // `x` and `y` share a slot, so there's room for 2 variables below the saved rbx, padded
+        // so the stack stays aligned
+        assert!(contains_in_order(&instructions, &["main:", "push rbp", "mov rbp, rsp", "push rbx", "sub rsp, 24"]));
+        assert!(contains_in_order(&instructions, &["mov rdi, 2", "mov rax, rdi", "mov QWORD [rbp-24], rax"]));
+        assert!(contains_in_order(&instructions, &["mov rdi, QWORD [rbp-16]", "mov rax, rdi", "mov QWORD [rbp-24], rax"]));
+        assert!(contains_in_order(&instructions, &["zero:", "push rbp", "mov rbp, rsp", "push rbx", "sub rsp, 8"]));
+    }
+
+    #[test]
+    fn nested_blocks() {
+        let instructions = assemble_file(&[
+            "subroutine main::[] -> int :",
+            "    decl n:=[int] = 0",
+            "    cmpr :&[n] :",
+            "        [n < 1] :",
+            "            repeat :*[n < 3] :",
+            "                repeat :*[n < 2] :",
+            "                    chng n = n + 1",
+            "                ;",
+            "                chng n = n + 1",
+            "            ;",
+            "        ;",
+            "        [n == 1] :",
+            "            cmpr :&[n] :",
+            "                [n > 0] :",
+            "                    chng n = 2",
+            "                ;",
+            "            ;",
+            "        ;",
+            "    ;",
+            "    expose n",
+            ";",
+        ].join("\n"));
+
+        // Every nested `cmpr` and `repeat` gets labels of its own
+        let labels: Vec<&String> = instructions.iter().filter(|x| x.ends_with(':')).collect();
+        assert_eq!(labels.len(), labels.iter().collect::<std::collections::HashSet<_>>().len());
+        for label in ["cmpr_branch_0:", "cmpr_branch_1:", "cmpr_branch_2:", "cmpr_end_0:", "cmpr_end_1:", "repeat_0:", "repeat_1:", "repeat_end_0:", "repeat_end_1:"] {
+            assert!(instructions.iter().any(|x| x == label), "missing {label}");
         }
+
+        // The inner loop jumps back to its own head, and leaves to the rest of the outer loop
+        assert!(contains_in_order(&instructions, &["jmp repeat_1", "repeat_end_1:"]));
+        assert!(contains_in_order(&instructions, &["jmp repeat_0", "repeat_end_0:", "jmp cmpr_end_0"]));
+    }
+
+    #[test]
+    fn conditions() {
+        let instructions = assemble_file(&[
+            "subroutine small::[int n] -> tof :",
+            "    expose n < 5",
+            ";",
+            "subroutine main::[] -> int :",
+            "    decl e:=[tof] = false",
+            "    decl n:=[int] = 0",
+            "    repeat :*[e] :",
+            "        chng n = n + 1",
+            "    ;",
+            "    cmpr :&[n] :",
+            "        [small::[n]] :",
+            "            chng n = 2",
+            "        ;",
+            "    ;",
+            "    expose n",
+            ";",
+        ].join("\n"));
+
+        // A `tof` that isn't a comparison is evaluated into rax and tested
+        assert!(contains_in_order(&instructions, &["repeat_0:", "mov rax, QWORD [rbp-16]", "test rax, rax", "jz repeat_end_0"]));
+        assert!(contains_in_order(&instructions, &["call small", "lea rsp, [rbx+8]", "test rax, rax", "jnz cmpr_branch_0"]));
+    }
+
+    #[test]
+    fn tokenizer_errors() {
+        // Typos are reported with the span at fault rather than panicking
+        let error = tokenize_file("subroutine main::[] -> int :\n    decl x:=[int] = y\n;\n").unwrap_err();
+        assert_eq!((error.code(), error.span()), ("E0114", Some(Span::new(0, 49, 50, 2, 21))));
+
+        let error = tokenize_file("subroutine main::[] -> int :\n    decl x:=[nt] = 1\n;\n").unwrap_err();
+        assert_eq!((error.code(), error.span()), ("E0108", Some(Span::new(0, 42, 44, 2, 14))));
+
+        // An empty argument points at the argument list it's missing from
+        let error = tokenize_file("subroutine main::[int a,] -> int :\n    expose a\n;\n").unwrap_err();
+        assert_eq!((error.code(), error.span()), ("E0110", Some(Span::new(0, 15, 25, 1, 16))));
+
+        let error = tokenize_file("subroutine main::[] int :\n    expose 1\n;\n").unwrap_err();
+        assert_eq!(error.code(), "E0116");
+
+        let error = tokenize_file("subroutine main::[] -> int :\n    expose 1 ;\n").unwrap_err();
+        assert_eq!(error.code(), "E0113");
+
+        // Integers that don't fit in an int are never turned into a flt
+        let error = tokenize_file("subroutine main::[] -> int :\n    decl x:=[int] = 9223372036854775808\n    expose x\n;\n").unwrap_err();
+        assert_eq!((error.code(), error.span()), ("E0128", Some(Span::new(0, 49, 68, 2, 21))));
+
+        // `break` and `continue` only work inside a loop, with the label of a loop around them
+        let error = tokenize_file("subroutine main::[] -> int :\n    break\n    expose 1\n;\n").unwrap_err();
+        assert_eq!((error.code(), error.span()), ("E0121", Some(Span::new(0, 33, 38, 2, 5))));
+
+        let error = tokenize_file("subroutine main::[] -> int :\n    repeat outer :*[true] :\n        continue inner\n    ;\n    expose 1\n;\n").unwrap_err();
+        assert_eq!(error.code(), "E0122");
+        assert!(tokenize_file("subroutine main::[] -> int :\n    repeat outer :*[true] :\n        loop i:=[int] from 0 to 2 :\n            continue outer\n        ;\n    ;\n    expose 1\n;\n").is_ok());
+    }
+
+    #[test]
+    fn terminating_loop() {
+        // `step` is 1 unless it's given
+        let token_tree = tokenize_file("subroutine main::[int n] -> int :\n    loop i:=[int] from 0 to n :\n        chng n = n + i\n    ;\n    expose n\n;\n").unwrap();
+        let Token::Function(function) = &token_tree[0] else { panic!("expected a function") };
+        let Token::TerminatingLoop(terminating_loop) = &function.functionaliy[0] else { panic!("expected a loop") };
+        assert!(matches!((&terminating_loop.from, &terminating_loop.to, &terminating_loop.step), (Assignment::INTEGER(0, _), Assignment::VAR(0, _), Assignment::INTEGER(1, _))));
+        assert_eq!(terminating_loop.location, 1);
+
+        // The loop variable can't be used once the loop ends
+        let error = tokenize_file("subroutine main::[] -> int :\n    loop i:=[int] from 0 to 3 step 2 :\n        decl x:=[int] = i\n    ;\n    expose i\n;\n").unwrap_err();
+        assert_eq!((error.code(), error.span()), ("E0123", Some(Span::new(0, 111, 112, 5, 12))));
+    }
+
+    #[test]
+    fn scopes() {
+        // A `decl` in a `cmpr` branch can't be used after the `cmpr`
+        let error = tokenize_file("subroutine main::[tof a] -> int :\n    cmpr :&[a] :\n        [a] :\n            decl x:=[int] = 1\n        ;\n    ;\n    expose x\n;\n").unwrap_err();
+        assert_eq!(error.code(), "E0123");
+
+        // Variables in scopes that ended share their slots, and an inner variable shadows an
+        // outer one until its scope ends
+        let token_tree = tokenize_file("subroutine main::[tof a] -> int :\n    decl x:=[int] = 1\n    cmpr :&[a, x] :\n        [a] :\n            decl x:=[flt] = 2.5\n            decl y:=[flt] = x\n        ;\n        [otherwise] :\n            decl z:=[int] = x\n        ;\n    ;\n    expose x\n;\n").unwrap();
+        let Token::Function(function) = &token_tree[0] else { panic!("expected a function") };
+        let slots: Vec<usize> = function.variable_history.data.iter().flatten().map(|x| x.slot).collect();
+        assert_eq!(slots, vec![0, 1, 2, 3, 2]);
+        assert_eq!(function.variable_history.slot_count(), 4);
+        assert!(TypeChecker::init().check_token_tree(&token_tree).is_ok());
+
+        // A name can only be declared once in the same block, pointing at both declarations
+        let error = tokenize_file("subroutine main::[int n] -> int :\n    decl n:=[int] = 1\n    expose n\n;\n").unwrap_err();
+        assert_eq!((error.code(), error.span()), ("E0124", Some(Span::new(0, 43, 44, 2, 10))));
+        assert_eq!(error.secondary_label().map(|x| x.0), Some(Span::new(0, 22, 23, 1, 23)));
+
+        let error = tokenize_file("subroutine main::[] -> int :\n    expose 1\n;\nsubroutine main::[] -> int :\n    expose 2\n;\n").unwrap_err();
+        assert_eq!(error.code(), "E0125");
+    }
+
+    #[test]
+    fn type_errors() {
+        let check = |file_content: &str| {
+            return TypeChecker::init().check_token_tree(&tokenize_file(file_content).unwrap())
+                .map_err(|x| x.iter().map(|x| (x.code(), x.span())).collect::<Vec<_>>())
+        };
+
+        // A `flt` is never stored in an `int` without an `as int`
+        let errors = check("subroutine main::[] -> int :\n    decl x:=[int] = 1 + 0.5\n    expose x\n;\n").unwrap_err();
+        assert_eq!(errors, vec![("E0300", Span::new(0, 49, 56, 2, 21))]);
+        assert!(check("subroutine main::[] -> int :\n    decl x:=[int] = (1 + 0.5) as int\n    expose x\n;\n").is_ok());
+
+        // Every mistake is reported once, without the statements around it being reported too
+        let errors = check("subroutine main::[] -> tof :\n    decl x:=[flt] = 2 % 1.5\n    repeat :*[1 + 2] :\n        chng x = not x\n    ;\n    expose 1\n;\n").unwrap_err();
+        assert_eq!(errors.iter().map(|x| x.0).collect::<Vec<_>>(), vec!["E0301", "E0300", "E0302", "E0300"]);
+
+        // Calls are checked against the subroutine they call
+        let errors = check("subroutine add::[int a, flt b] -> int :\n    expose a\n;\nsubroutine main::[] -> int :\n    decl x:=[int] = add::[1]\n    decl y:=[int] = add::[1, true]\n    expose add::[1, 2]\n;\n").unwrap_err();
+        assert_eq!(errors.iter().map(|x| x.0).collect::<Vec<_>>(), vec!["E0304", "E0300"]);
+
+        // A `cmpr` without an `otherwise` branch may not run any of its `expose`s
+        let errors = check("subroutine main::[] -> int :\n    decl x:=[int] = 1\n    cmpr :&[x] :\n        [x == 1] :\n            expose 1\n        ;\n    ;\n;\n").unwrap_err();
+        assert_eq!(errors, vec![("E0305", Span::new(0, 0, 125, 1, 1))]);
+        assert!(check("subroutine main::[] -> int :\n    decl x:=[int] = 1\n    cmpr :&[x] :\n        [x == 1] :\n            expose 1\n        ;\n        [otherwise] :\n            expose 0\n        ;\n    ;\n;\n").is_ok());
+    }
+
+    #[test]
+    fn precedence() {
+        // Writes an Assignment out with every equation in brackets
+        fn group(assignment: &Assignment) -> String { match assignment {
+            Assignment::EVAL(first, operator, second, _)  => { return format!("({} {:?} {})", group(first), operator, group(second)) }
+            Assignment::CMP(first, operator, second, _)   => { return format!("({} {:?} {})", group(first), operator, group(second)) }
+            Assignment::LOGIC(first, operator, second, _) => { return format!("({} {:?} {})", group(first), operator, group(second)) }
+            Assignment::UNARY(operator, term, _)          => { return format!("({:?} {})", operator, group(term)) }
+            Assignment::VAR(location, _)                  => { return format!("v{}", location) }
+            Assignment::INTEGER(value, _)                 => { return value.to_string() }
+            _                                             => { return format!("{:?}", assignment) }
+        }}
+
+        let file_content = "subroutine main::[] -> int :\n    decl a:=[int] = 1\n    decl x:=[tof] = a + a * 2 - 1 < a * 3\n    decl y:=[int] = (a + 2) * (a - (1 - a))\n    decl z:=[tof] = not -a < -(2)\n    decl w:=[tof] = a == 1 or a > 2 and not a < 3\n    decl u:=[tof] = not a == 1 and not a > 2\n    decl v:=[int] = a << 1 + a & 3 | a >> 2 ^ ~a\n    decl m:=[int] = -9223372036854775808\n    expose a\n;\n";
+        let token_tree = tokenize_file(file_content).unwrap();
+        let Token::Function(function) = &token_tree[0] else { panic!("expected a function") };
+        let Token::Declaration(declaration) = &function.functionaliy[1] else { panic!("expected a declaration") };
+        assert_eq!(group(&declaration.value), "(((v0 ADD (v0 MUL 2)) SUB 1) LT (v0 MUL 3))");
+
+        // Groups are parsed before anything outside of them
+        let Token::Declaration(declaration) = &function.functionaliy[2] else { panic!("expected a declaration") };
+        assert_eq!(group(&declaration.value), "((v0 ADD 2) MUL (v0 SUB (1 SUB v0)))");
+
+        // `-` and `~` only hold onto the term right after them, while `not` holds onto the whole
+        // comparison after it
+        let Token::Declaration(declaration) = &function.functionaliy[3] else { panic!("expected a declaration") };
+        assert_eq!(group(&declaration.value), "(NOT ((NEG v0) LT -2))");
+
+        // `and` holds on tighter than `or`, and both are applied after comparisons and `not`
+        let Token::Declaration(declaration) = &function.functionaliy[4] else { panic!("expected a declaration") };
+        assert_eq!(group(&declaration.value), "((v0 EQ 1) OR ((v0 GT 2) AND (NOT (v0 LT 3))))");
+
+        // `not` stops at `and` and `or`
+        let Token::Declaration(declaration) = &function.functionaliy[5] else { panic!("expected a declaration") };
+        assert_eq!(group(&declaration.value), "((NOT (v0 EQ 1)) AND (NOT (v0 GT 2)))");
+
+        // `<<` and `>>` are never split into two comparisons
+        let Token::Declaration(declaration) = &function.functionaliy[6] else { panic!("expected a declaration") };
+        assert_eq!(group(&declaration.value), "(((v0 SHL (1 ADD v0)) BAND 3) BOR ((v0 SHR 2) BXOR (BNOT v0)))");
+
+        // The smallest int is still an int, even though it only fits with its sign
+        let Token::Declaration(declaration) = &function.functionaliy[7] else { panic!("expected a declaration") };
+        assert_eq!(group(&declaration.value), "-9223372036854775808");
+    }
+
+    #[test]
+    fn calling_convention() {
+        use crate::tokenizer::declaration::DataType::{BOOL, FLOAT, INTEGER};
+
+        // `int` and `flt` arguments fill their own registers, and spill onto the stack in order
+        let locations = locate_arguments(&[INTEGER, FLOAT, BOOL, INTEGER, INTEGER, INTEGER, INTEGER, INTEGER, FLOAT, INTEGER]);
+        assert_eq!(locations, vec![
+            ArgumentLocation::IntegerRegister("rdi"), ArgumentLocation::FloatRegister("xmm0"), ArgumentLocation::IntegerRegister("rsi"),
+            ArgumentLocation::IntegerRegister("rdx"), ArgumentLocation::IntegerRegister("rcx"), ArgumentLocation::IntegerRegister("r8"),
+            ArgumentLocation::IntegerRegister("r9"), ArgumentLocation::Stack(0), ArgumentLocation::FloatRegister("xmm1"),
+            ArgumentLocation::Stack(1),
+        ]);
+    }
+
+    #[test]
+    fn labels() {
+        let mut labels = LabelAllocator::init();
+        labels.reserve("repeat_1");
+        labels.reserve("int_divide");
+
+        // Reserved labels are skipped, and helpers are only renamed if their name is taken
+        assert_eq!([labels.allocate("repeat"), labels.allocate("repeat"), labels.allocate("repeat")], ["repeat_0", "repeat_2", "repeat_3"]);
+        assert_eq!([labels.helper("int_divide"), labels.helper("int_divide"), labels.helper("cmp_eq")], ["int_divide_0", "int_divide_0", "cmp_eq"]);
+    }
+
+    #[test]
+    fn error_recovery() {
+        let file_content = [
+            "decl y:=[int] = 1",
+            "bogus stuff here",
+            "subroutin other::[] -> int :",
+            "    expose 0",
+            ";",
+            "subroutine helper::[int a,] -> int :",
+            "    expose a",
+            ";",
+            "subroutine main::[] -> int :",
+            "    decl x:=[int] = y",
+            "    chng w = 2",
+            "    chnge x = 5",
+            "    cmpr :&[x] :",
+            "        [x == bogus] :",
+            "            decl k:=[nt] = 1",
+            "        ;",
+            "    ;",
+            "    exposee x",
+            "    expose x",
+            ";",
+            "",
+        ].join("\n");
+
+        // Every broken statement is reported, in order, and `x` is still usable afterwards
+        let errors = tokenize_all(&file_content).unwrap_err();
+        let lines: Vec<usize> = errors.iter().map(|x| x.span().unwrap().line).collect();
+        assert_eq!(lines, vec![1, 2, 3, 6, 10, 11, 12, 14, 15, 18]);
+    }
+
+    #[test]
+    fn render_diagnostic() {
+        colored::control::set_override(false);
+
+        let mut source_map = SourceMap::init();
+        let file_id = source_map.add_file("main.uml", "subroutine main::[] -> int :\n    decl x:=[int] = y\n;\n");
+
+        let rendered = Diagnostic::error("use of undeclared variable `y`")
+            .with_code("E0104")
+            .with_label(Span::new(file_id, 49, 50, 2, 21), "not found in this function")
+            .with_secondary_label(Span::new(file_id, 0, 10, 1, 1), "in this function")
+            .with_note("variables must be declared before they are used")
+            .with_help("declare it first with `decl y:=[int] = 0`")
+            .render(&source_map);
+
+        assert_eq!(rendered, [
+            "error[E0104]: use of undeclared variable `y`",
+            " --> main.uml:2:21",
+            "  |",
+            "1 | subroutine main::[] -> int :",
+            "  | ---------- in this function",
+            "2 |     decl x:=[int] = y",
+            "  |                     ^ not found in this function",
+            "  |",
+            "  = note: variables must be declared before they are used",
+            "  = help: declare it first with `decl y:=[int] = 0`",
+            "",
+        ].join("\n"));
+
+        // A span without a location is left out rather than quoted
+        let rendered = Diagnostic::error("expected a name")
+            .with_code("E0110")
+            .with_label(Span::default(), "expected a name here")
+            .with_help("give it a name")
+            .render(&source_map);
+
+        assert_eq!(rendered, [
+            "error[E0110]: expected a name",
+            " = help: give it a name",
+            "",
+        ].join("\n"));
     }
-    */
 }
@@ -2,8 +2,12 @@ use crate::data::SyntaxElements;
 use crate::type_traits::vector::StringVecExtra;
 
 
+pub mod span;
+    use span::{Span, Word};
+
+
 pub struct Optimizer {
-    pub content: Vec<String>,
+    pub content: Vec<Word>,
 
     syntax_elements: SyntaxElements,
 
@@ -13,53 +17,89 @@ pub struct Optimizer {
         syntax_elements: SyntaxElements::init(),
     }}
 
-    pub fn generate_optimized_content(&mut self, file_content: &str) {
-        // Replace all newline and tab characters eith spaces
-        let script_content_flattened: Vec<char> = file_content
-            //.replace("\n", " ")
-            .replace("\t", " ")
-            .chars().collect();
-
-        // Seperate all characters in the symbols list with spaces
-        fn seperate_symbols(syntax_elements: &SyntaxElements, vector: Vec<char>, current_index: usize) -> String {
-            if current_index == vector.len() - 1 {
-                return vector.iter().collect();
+    /// Flattens `file_content` into a list of words, where every word keeps the span of the
+    /// source text it was taken from
+    ///
+    /// `file_id` is stored in each span so diagnostics can find the file the word belongs to
+    pub fn generate_optimized_content(&mut self, file_id: usize, file_content: &str) {
+        let all_symbols_sorted: Vec<Vec<char>> = self.syntax_elements.get_all_symbols().sort_by_size().iter()
+            .map(|x| x.chars().collect())
+            .collect();
+
+        // Keep the byte offset of every character so the spans can point back into the file
+        let characters: Vec<(usize, char)> = file_content.char_indices().collect();
+
+        let mut result: Vec<Word> = Vec::new();
+
+        // The word currently being built and the span it started at
+        let mut current_word = String::new();
+        let mut current_word_start: Option<Span> = None;
+
+        // Pushes the word currently being built (if there is one) to the result
+        fn finish_word(result: &mut Vec<Word>, current_word: &mut String, current_word_start: &mut Option<Span>, end: usize) {
+            if let Some(start) = current_word_start.take() {
+                let span = Span::new(start.file_id, start.start, end, start.line, start.column);
+                result.push(Word::new(current_word, span));
+                current_word.clear();
             }
+        }
+
+        let mut line: usize = 1;
+        let mut column: usize = 1;
+
+        let mut i: usize = 0;
+        'characters: while i < characters.len() {
+            let (byte_offset, character) = characters[i];
 
-            let all_symbols_sorted = syntax_elements.get_all_symbols().sort_by_size();
+            // Spaces and tabs only seperate words
+            if character == ' ' || character == '\t' {
+                finish_word(&mut result, &mut current_word, &mut current_word_start, byte_offset);
 
-            for symbol in all_symbols_sorted {
-                if symbol.len() > vector[current_index..].len() {
+                column += 1;
+                i += 1;
+                continue;
+            }
+
+            // Seperate every symbol into its own word (checking the longest symbols first)
+            for symbol in all_symbols_sorted.iter() {
+                if symbol.len() > characters[i..].len() {
                     continue;
                 }
 
-                let chars_to_scan = vector[current_index..current_index + symbol.len()].to_vec();
-                let word_to_scan: String = chars_to_scan.iter().collect();
+                let is_match = characters[i..i + symbol.len()].iter()
+                    .zip(symbol.iter())
+                    .all(|((_, found), expected)| found == expected);
+
+                if is_match {
+                    finish_word(&mut result, &mut current_word, &mut current_word_start, byte_offset);
 
-                if symbol == word_to_scan {
-                    let mut updated_vector = vector.clone();
-                    updated_vector.insert(current_index, ' ');
-                    updated_vector.insert(current_index + symbol.len() + 1, ' ');
+                    let symbol_text: String = symbol.iter().collect();
+                    let end = byte_offset + symbol_text.len();
+                    result.push(Word::new(&symbol_text, Span::new(file_id, byte_offset, end, line, column)));
 
-                    return seperate_symbols(syntax_elements, updated_vector, current_index + symbol.len() + 2);
+                    // Keep the line and column up to date with the symbols passed
+                    for symbol_character in symbol.iter() {
+                        if *symbol_character == '\n' { line += 1; column = 1 }
+                        else                         { column += 1 }
+                    }
+
+                    i += symbol.len();
+                    continue 'characters;
                 }
             }
 
-            return seperate_symbols(syntax_elements, vector, current_index + 1);
-        }
-        let script_content_seperate_symbols = seperate_symbols(&self.syntax_elements, script_content_flattened, 0);
+            // Otherwise, this character is part of a word
+            if current_word_start.is_none() {
+                current_word_start = Some(Span::new(file_id, byte_offset, byte_offset, line, column));
+            }
+            current_word.push(character);
 
-        // Split by spaces
-        let script_content_split = script_content_seperate_symbols.split(" ");
+            column += 1;
+            i += 1;
+        }
+        finish_word(&mut result, &mut current_word, &mut current_word_start, file_content.len());
 
-        // Remove empty chars created by multiple spaces
-        let script_content_trimmed: Vec<&str> = script_content_split.into_iter()
-            .filter_map(|x| if x != "" {Some(x)} else {None})
-            .collect();
-        
         // Store the result into content
-        self.content = script_content_trimmed.iter()
-            .map(|x| x.to_string())
-            .collect();
+        self.content = result;
     }
 }
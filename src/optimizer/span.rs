@@ -0,0 +1,109 @@
+use std::fmt;
+use std::ops::Deref;
+
+
+/// The location of a piece of source text
+///
+/// `start` and `end` are byte offsets into the file (`end` is exclusive) while `line` and
+/// `column` are 1-based and point at the first character of the span
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub file_id: usize,
+    pub start: usize,
+    pub end: usize,
+    pub line: usize,
+    pub column: usize,
+} impl Span {
+    pub fn new(file_id: usize, start: usize, end: usize, line: usize, column: usize) -> Self { Self {
+        file_id,
+        start,
+        end,
+        line,
+        column,
+    }}
+
+    /// Returns a span covering both `self` and `other`
+    ///
+    /// The line and column are taken from whichever span starts first
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// // `decl` and `1` in `decl x:=[int] = 1`
+    /// let first  = Span::new(0, 0, 4, 1, 1);
+    /// let second = Span::new(0, 17, 18, 1, 18);
+    ///
+    /// // Span { file_id: 0, start: 0, end: 18, line: 1, column: 1 }
+    /// println!("{:?}", first.merge(&second));
+    /// ```
+    pub fn merge(&self, other: &Span) -> Self {
+        let first = if self.start <= other.start { self } else { other };
+
+        return Self {
+            file_id: self.file_id,
+            start: first.start,
+            end: self.end.max(other.end),
+            line: first.line,
+            column: first.column,
+        }
+    }
+}
+
+
+/// A single word/symbol produced by the `Optimizer` along with where it came from
+///
+/// `Word` dereferences to its text so it can be compared and parsed like the `String`s the
+/// `Optimizer` used to produce
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Word {
+    pub text: String,
+    pub span: Span,
+} impl Word {
+    pub fn new(text: &str, span: Span) -> Self { Self {
+        text: text.to_string(),
+        span,
+    }}
+}
+
+impl Deref for Word {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        return &self.text
+    }
+}
+
+impl PartialEq<str> for Word {
+    fn eq(&self, other: &str) -> bool {
+        return self.text == other
+    }
+}
+
+impl PartialEq<String> for Word {
+    fn eq(&self, other: &String) -> bool {
+        return &self.text == other
+    }
+}
+
+impl fmt::Display for Word {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+
+pub trait WordSliceExtra {
+    /// Returns the span running from the first word in this slice to the last
+    ///
+    /// Returns `Span::default()` if the slice is empty
+    fn span(&self) -> Span;
+}
+
+impl WordSliceExtra for [Word] {
+    fn span(&self) -> Span {
+        match (self.first(), self.last()) {
+            (Some(first), Some(last)) => { return first.span.merge(&last.span) }
+            _                         => { return Span::default() }
+        }
+    }
+}
@@ -1,9 +1,12 @@
 use super::enumerators::Assignment;
 use super::Token;
+use crate::optimizer::span::Span;
 
 
 #[derive(Clone, Debug)]
 pub struct ConditionalLoop {
+    pub label: Option<String>,
     pub condition: Assignment,
     pub functionality: Vec<Token>,
+    pub span: Span,
 }
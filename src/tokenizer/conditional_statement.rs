@@ -1,9 +1,11 @@
 use super::enumerators::Assignment;
 use super::Token;
+use crate::optimizer::span::Span;
 
 
 #[derive(Clone, Debug)]
 pub struct ConditionalStatement {
     pub active_variables: Vec<usize>,
     pub condition_fields: Vec<(Option<Assignment>, Vec<Token>)>,
+    pub span: Span,
 }
@@ -1,8 +1,9 @@
 use super::enumerators::Assignment;
 use crate::data::SyntaxElements;
+use crate::optimizer::span::Span;
 
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum DataType {
     INTEGER,
     FLOAT,
@@ -15,6 +16,13 @@ pub enum DataType {
                                                           _ => None,
     }}
 
+    /// The name this type is written with
+    pub fn to_type_name(&self) -> String { let syntax_elements = SyntaxElements::init(); match self {
+        Self::INTEGER => syntax_elements.type_names["integer"].clone(),
+        Self::FLOAT   => syntax_elements.type_names["float"].clone(),
+        Self::BOOL    => syntax_elements.type_names["boolean"].clone(),
+    }}
+
     pub fn is_integer(&self) -> bool {
         if let Self::INTEGER = self {
             return true
@@ -38,4 +46,5 @@ pub struct Declaration {
     pub location: usize,
     pub data_type: DataType,
     pub value: Assignment,
+    pub span: Span,
 }
@@ -5,6 +5,7 @@ use crate::type_traits::hashmap::HashMapExtra;
 use super::structures::VariableHistory;
 use crate::type_traits::vector::VecExtra;
 use super::declaration::DataType;
+use crate::optimizer::span::{Span, Word, WordSliceExtra};
 
 
 #[derive(Debug, Clone, PartialEq)]
@@ -13,6 +14,17 @@ pub enum MathOperator {
     SUB,
     MUL,
     DIV,
+    MOD,
+    /// Bitwise And
+    BAND,
+    /// Bitwise Or
+    BOR,
+    /// Bitwise Exclusive Or
+    BXOR,
+    /// Shift Left
+    SHL,
+    /// Shift Right (keeping the sign)
+    SHR,
 } impl MathOperator {
     /// Converts a string of the accociated operator with a variant of `Operator`
     ///
@@ -34,11 +46,61 @@ pub enum MathOperator {
             val if val == syntax_elements.math_symbols["division"] => {
                 return Ok(Self::DIV)
             }
+            val if val == syntax_elements.math_symbols["modulo"] => {
+                return Ok(Self::MOD)
+            }
+            val if val == syntax_elements.math_symbols["bitwise and"] => {
+                return Ok(Self::BAND)
+            }
+            val if val == syntax_elements.math_symbols["bitwise or"] => {
+                return Ok(Self::BOR)
+            }
+            val if val == syntax_elements.math_symbols["bitwise xor"] => {
+                return Ok(Self::BXOR)
+            }
+            val if val == syntax_elements.math_symbols["shift left"] => {
+                return Ok(Self::SHL)
+            }
+            val if val == syntax_elements.math_symbols["shift right"] => {
+                return Ok(Self::SHR)
+            }
             _ => {
                 return Err(TokenizerError::CouldNotParseMathOperator)
             }
         }
     }
+
+    /// How tightly this operator holds onto its terms. Operators with a higher precedence are
+    /// evaluated first
+    pub fn precedence(&self) -> usize { match self {
+        Self::BOR  => { return 4 }
+        Self::BXOR => { return 5 }
+        Self::BAND => { return 6 }
+        Self::SHL  => { return 7 }
+        Self::SHR  => { return 7 }
+        Self::ADD  => { return 8 }
+        Self::SUB  => { return 8 }
+        Self::MUL  => { return 9 }
+        Self::DIV  => { return 9 }
+        Self::MOD  => { return 9 }
+    }}
+
+    /// The symbol this operator is written with
+    pub fn to_symbol(&self) -> String {
+        let syntax_elements = SyntaxElements::init();
+        match self {
+            Self::ADD  => { return syntax_elements.math_symbols["addition"].clone()       }
+            Self::SUB  => { return syntax_elements.math_symbols["subtraction"].clone()    }
+            Self::MUL  => { return syntax_elements.math_symbols["multiplication"].clone() }
+            Self::DIV  => { return syntax_elements.math_symbols["division"].clone()       }
+            Self::MOD  => { return syntax_elements.math_symbols["modulo"].clone()         }
+            Self::BAND => { return syntax_elements.math_symbols["bitwise and"].clone()    }
+            Self::BOR  => { return syntax_elements.math_symbols["bitwise or"].clone()     }
+            Self::BXOR => { return syntax_elements.math_symbols["bitwise xor"].clone()    }
+            Self::SHL  => { return syntax_elements.math_symbols["shift left"].clone()     }
+            Self::SHR  => { return syntax_elements.math_symbols["shift right"].clone()    }
+        }
+    }
 }
 
 
@@ -84,6 +146,25 @@ pub enum ComparisonOperator {
         }
     }
 
+    /// The symbol this operator is written with
+    pub fn to_symbol(&self) -> String {
+        let syntax_elements = SyntaxElements::init();
+        match self {
+            Self::EQ  => { return syntax_elements.comparision_symbols["equal to"].clone()                 }
+            Self::NEQ => { return syntax_elements.comparision_symbols["not equal to"].clone()             }
+            Self::GT  => { return syntax_elements.comparision_symbols["greater than"].clone()             }
+            Self::GEQ => { return syntax_elements.comparision_symbols["greater than or equal to"].clone() }
+            Self::LT  => { return syntax_elements.comparision_symbols["less than"].clone()                }
+            Self::LEQ => { return syntax_elements.comparision_symbols["less than or equal to"].clone()    }
+        }
+    }
+
+    /// How tightly this operator holds onto its terms. Comparisons are evaluated after all
+    /// arithmetic but before `and` and `or`
+    pub fn precedence(&self) -> usize {
+        return 3
+    }
+
     /// Returns the polar opposite operator of `self`
     pub fn negation(&self) -> Self { match self {
         Self::EQ  => { return Self::NEQ }
@@ -96,72 +177,339 @@ pub enum ComparisonOperator {
 }
 
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogicalOperator {
+    AND,
+    OR,
+} impl LogicalOperator {
+    /// Converts a string of the accociated operator into a `LogicalOperator`
+    ///
+    /// Returns None if `from` is not a logical operator
+    pub fn from_string(from: &str) -> Option<Self> {
+        let syntax_elements = SyntaxElements::init();
+        match from {
+            val if val == syntax_elements.operator_names["and"] => {
+                return Some(Self::AND)
+            }
+            val if val == syntax_elements.operator_names["or"] => {
+                return Some(Self::OR)
+            }
+            _ => {
+                return None
+            }
+        }
+    }
+
+    /// The name this operator is written with
+    pub fn to_symbol(&self) -> String {
+        let syntax_elements = SyntaxElements::init();
+        match self {
+            Self::AND => { return syntax_elements.operator_names["and"].clone() }
+            Self::OR  => { return syntax_elements.operator_names["or"].clone()  }
+        }
+    }
+
+    /// How tightly this operator holds onto its terms. `and` holds on tighter than `or`, and
+    /// both are evaluated after everything else
+    pub fn precedence(&self) -> usize { match self {
+        Self::AND => { return 2 }
+        Self::OR  => { return 1 }
+    }}
+}
+
+
+/// Any operator that sits between two terms of an equation
+#[derive(Debug, Clone)]
+pub enum BinaryOperator {
+    Math(MathOperator),
+    Comparison(ComparisonOperator),
+    Logical(LogicalOperator),
+} impl BinaryOperator {
+    /// Converts a string of the accociated operator into a `BinaryOperator`
+    ///
+    /// Returns None if `from` is not an operator
+    pub fn from_string(from: &str) -> Option<Self> {
+        if let Ok(math_operator) = MathOperator::from_string(from) {
+            return Some(Self::Math(math_operator))
+        }
+        if let Ok(comparison_operator) = ComparisonOperator::from_string(from) {
+            return Some(Self::Comparison(comparison_operator))
+        }
+        if let Some(logical_operator) = LogicalOperator::from_string(from) {
+            return Some(Self::Logical(logical_operator))
+        }
+
+        return None
+    }
+
+    pub fn precedence(&self) -> usize { match self {
+        Self::Math(math_operator)             => { return math_operator.precedence()       }
+        Self::Comparison(comparison_operator) => { return comparison_operator.precedence() }
+        Self::Logical(logical_operator)       => { return logical_operator.precedence()    }
+    }}
+
+    /// Builds the Assignment applying this operator to `first_term` and `second_term`
+    ///
+    /// If one term of a math operator or comparison is a `flt`, the other term is promoted to a
+    /// `flt` as well
+    pub fn to_assignment(&self, first_term: Assignment, second_term: Assignment, variable_history: &VariableHistory) -> Result<Assignment, TokenizerError> {
+        let span = first_term.span().merge(&second_term.span());
+
+        let (first_term, second_term) = match self {
+            Self::Math(_) | Self::Comparison(_) if first_term.evaluate_type(variable_history).is_float() => {
+                (first_term, second_term.promote_to(&DataType::FLOAT, variable_history))
+            }
+            Self::Math(_) | Self::Comparison(_) if second_term.evaluate_type(variable_history).is_float() => {
+                (first_term.promote_to(&DataType::FLOAT, variable_history), second_term)
+            }
+            _ => { (first_term, second_term) }
+        };
+
+        match self {
+            Self::Math(math_operator) => {
+                return Ok(Assignment::EVAL(Box::new(first_term), math_operator.clone(), Box::new(second_term), span))
+            }
+            Self::Comparison(comparison_operator) => {
+                return Ok(Assignment::CMP(Box::new(first_term), comparison_operator.clone(), Box::new(second_term), span))
+            }
+            Self::Logical(logical_operator) => {
+                return Ok(Assignment::LOGIC(Box::new(first_term), logical_operator.clone(), Box::new(second_term), span))
+            }
+        }
+    }
+}
+
+
+/// Any operator that sits in front of a single term
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnaryOperator {
+    /// Negation of an `int` or `flt`
+    NEG,
+    /// Logical not of a `tof`
+    NOT,
+    /// Bitwise not of an `int`
+    BNOT,
+} impl UnaryOperator {
+    /// Converts a string of the accociated operator into a `UnaryOperator`
+    ///
+    /// Returns None if `from` is not a unary operator
+    pub fn from_string(from: &str) -> Option<Self> {
+        let syntax_elements = SyntaxElements::init();
+        match from {
+            val if val == syntax_elements.math_symbols["subtraction"] => {
+                return Some(Self::NEG)
+            }
+            val if val == syntax_elements.operator_names["not"] => {
+                return Some(Self::NOT)
+            }
+            val if val == syntax_elements.math_symbols["bitwise not"] => {
+                return Some(Self::BNOT)
+            }
+            _ => {
+                return None
+            }
+        }
+    }
+
+    /// The symbol (or name) this operator is written with
+    pub fn to_symbol(&self) -> String {
+        let syntax_elements = SyntaxElements::init();
+        match self {
+            Self::NEG  => { return syntax_elements.math_symbols["subtraction"].clone() }
+            Self::NOT  => { return syntax_elements.operator_names["not"].clone()       }
+            Self::BNOT => { return syntax_elements.math_symbols["bitwise not"].clone() }
+        }
+    }
+
+    /// The lowest precedence an operator in the equation after this one can have and still be
+    /// part of its term
+    ///
+    /// `not` takes a whole comparison (so `not a < 3` is `not (a < 3)`) but stops at `and` and
+    /// `or`, while `-` and `~` only take the single term right after them
+    pub fn operand_precedence(&self) -> Option<usize> { match self {
+        Self::NOT  => { return Some(3) }
+        Self::NEG  => { return None    }
+        Self::BNOT => { return None    }
+    }}
+}
+
+
+/// Every variant ends with the span of the source text it was parsed from
 #[derive(Debug, Clone)]
 pub enum Assignment {
-    EVAL(Box<Assignment>, MathOperator, Box<Assignment>),
-    CMP(Box<Assignment>, ComparisonOperator, Box<Assignment>),
-    FUNC(String, DataType, Vec<Assignment>),
-    BOOL(i64),
-    VAR(usize),
-    INTEGER(i64),
-    FLOAT(f64),
+    EVAL(Box<Assignment>, MathOperator, Box<Assignment>, Span),
+    CMP(Box<Assignment>, ComparisonOperator, Box<Assignment>, Span),
+    LOGIC(Box<Assignment>, LogicalOperator, Box<Assignment>, Span),
+    UNARY(UnaryOperator, Box<Assignment>, Span),
+    CAST(Box<Assignment>, DataType, Span),
+    FUNC(String, DataType, Vec<Assignment>, Span),
+    BOOL(i64, Span),
+    VAR(usize, Span),
+    INTEGER(i64, Span),
+    FLOAT(f64, Span),
 } impl Assignment {
-    /// Parses a string vector (an equation) into an Assignment
-    pub fn from_string_vec(tokenizer: &Tokenizer, variable_history: &VariableHistory, string_equation: Vec<String>) -> Self {
+    /// Parses a word vector (an equation) into an Assignment
+    ///
+    /// Operators with a higher precedence are applied first (`*` `/` `%` before `+` `-` before
+    /// shifts before `&` before `^` before `|` before comparisons before `and` before `or`) and
+    /// operators with the same precedence are applied from left to right, so
+    /// `a + b * c - 1` becomes `EVAL(EVAL(a, ADD, EVAL(b, MUL, c)), SUB, 1)`
+    pub fn from_string_vec(tokenizer: &Tokenizer, variable_history: &VariableHistory, string_equation: Vec<Word>) -> Result<Self, TokenizerError> {
         println!("coding_language::tokenizer::enumerators::Assignment::from_string_vec(): {:?}", string_equation);
 
+        if string_equation.is_empty() {
+            return Err(TokenizerError::IncorrectEquationFormatting { span: Span::default() })
+        }
+
+        let mut position: usize = 0;
+        let result = Self::parse_expression(tokenizer, variable_history, &string_equation, &mut position, 0)?;
+
+        // Every word has to be used by the equation
+        if let Some(unexpected_word) = string_equation.get(position) {
+            return Err(TokenizerError::UnexpectedWord { word: unexpected_word.text.clone(), span: unexpected_word.span })
+        }
+
+        return Ok(result)
+    }
+
+    /// Parses the equation starting at `position` using precedence climbing, stopping at the
+    /// first operator with a precedence lower than `minimum_precedence`
+    ///
+    /// `position` is left at the first word not used by the parsed equation
+    fn parse_expression(tokenizer: &Tokenizer, variable_history: &VariableHistory, string_equation: &[Word], position: &mut usize, minimum_precedence: usize) -> Result<Self, TokenizerError> {
+        let mut result = Self::parse_term(tokenizer, variable_history, string_equation, position)?;
+
+        while let Some(operator) = string_equation.get(*position).and_then(|x| BinaryOperator::from_string(x)) {
+            if operator.precedence() < minimum_precedence {
+                break
+            }
+            *position += 1;
+
+            // Only operators holding on tighter than this one can take the second term, which
+            // keeps operators of the same precedence left associative
+            let second_term = Self::parse_expression(tokenizer, variable_history, string_equation, position, operator.precedence() + 1)?;
+
+            result = operator.to_assignment(result, second_term, variable_history)?;
+        }
+
+        return Ok(result)
+    }
+
+    /// Parses the singular term starting at `position`, along with every `as <type>` conversion
+    /// after it
+    fn parse_term(tokenizer: &Tokenizer, variable_history: &VariableHistory, string_equation: &[Word], position: &mut usize) -> Result<Self, TokenizerError> {
         let syntax_elements = SyntaxElements::init();
 
-        let operator_symbols = syntax_elements.get_all_operator_symbols();
+        let mut result = Self::parse_prefixed_term(tokenizer, variable_history, string_equation, position)?;
+
+        while string_equation.get(*position).is_some_and(|x| *x == syntax_elements.operator_names["cast"]) {
+            let Some(type_name) = string_equation.get(*position+1) else {
+                return Err(TokenizerError::MissingType { span: string_equation[*position].span })
+            };
+            let data_type = DataType::check_token_type(type_name)
+                .ok_or(TokenizerError::UnknownType { name: type_name.text.clone(), span: type_name.span })?;
+            *position += 2;
 
-        // If the equation given is just one element
-        if string_equation.len() == 1 || string_equation.find_from_vec(&operator_symbols).is_none() {
-            return Self::from_equation_term(tokenizer, variable_history, string_equation).unwrap()
+            let span = result.span().merge(&type_name.span);
+            result = result.convert_to(&data_type, variable_history, span);
         }
 
-        // Get the location of the math symbol
-        let operator_symbol_index = string_equation.find_from_vec(&operator_symbols).unwrap();
-        let operator_symbol = string_equation[operator_symbol_index].clone();
+        return Ok(result)
+    }
+
+    /// Parses the singular term starting at `position` (a number, variable, function call, a
+    /// grouped equation or any of these after `-` or `~`), or a `not` and the comparison after it
+    fn parse_prefixed_term(tokenizer: &Tokenizer, variable_history: &VariableHistory, string_equation: &[Word], position: &mut usize) -> Result<Self, TokenizerError> {
+        let syntax_elements = SyntaxElements::init();
+
+        // The equation ended right after an operator (or an opening group)
+        if *position >= string_equation.len() || string_equation[*position] == syntax_elements.assignment_symbols["end group"] {
+            return Err(TokenizerError::MissingValue { span: string_equation[position.saturating_sub(1)].span })
+        }
 
-        // Get the first and second terms
-        let first_term_slice  = string_equation[..=operator_symbol_index-1].to_owned();
-        let second_term_slice = string_equation[operator_symbol_index+1..].to_owned();
+        // A group is a whole equation of its own, parsed before anything outside of it
+        if string_equation[*position] == syntax_elements.assignment_symbols["begin group"] {
+            let begin_group_span = string_equation[*position].span;
+            *position += 1;
 
-        // Parse the first and second terms
-        let first_term  = Self::from_equation_term(tokenizer, variable_history, first_term_slice ).unwrap();
-        let second_term = Self::from_equation_term(tokenizer, variable_history, second_term_slice).unwrap();
+            let result = Self::parse_expression(tokenizer, variable_history, string_equation, position, 0)?;
 
-        // Return the evaluation of the first term and the second term operating with the given
-        // math symbol
-        if syntax_elements.math_symbols.contains_value(&operator_symbol) {
-            return Self::EVAL(Box::new(first_term), MathOperator::from_string(&operator_symbol).unwrap(), Box::new(second_term));
+            let Some(end_group) = string_equation.get(*position).filter(|x| **x == syntax_elements.assignment_symbols["end group"]) else {
+                return Err(TokenizerError::UnclosedGroup { span: begin_group_span })
+            };
+            *position += 1;
+
+            return Ok(result.with_span(begin_group_span.merge(&end_group.span)))
         }
-        else if syntax_elements.comparision_symbols.contains_value(&operator_symbol) {
-            return Self::CMP(Box::new(first_term), ComparisonOperator::from_string(&operator_symbol).unwrap(), Box::new(second_term))
+
+        // A unary operator holds onto the single term after it, or the equation after it up to
+        // its operand precedence
+        if let Some(operator) = UnaryOperator::from_string(&string_equation[*position]) {
+            let operator_span = string_equation[*position].span;
+            *position += 1;
+
+            // The smallest int only fits with its sign, so a negative integer literal is parsed whole
+            if let (UnaryOperator::NEG, Some(number)) = (&operator, string_equation.get(*position).filter(|x| Self::is_integer_literal(x))) {
+                let span = operator_span.merge(&number.span);
+                *position += 1;
+
+                let Ok(number) = format!("-{}", number.text).parse::<i64>() else {
+                    return Err(TokenizerError::IntegerOutOfRange { span })
+                };
+                return Ok(Self::INTEGER(number, span))
+            }
+
+            let term = match operator.operand_precedence() {
+                Some(minimum_precedence) => { Self::parse_expression(tokenizer, variable_history, string_equation, position, minimum_precedence)? }
+                None                     => { Self::parse_prefixed_term(tokenizer, variable_history, string_equation, position)? }
+            };
+            let span = operator_span.merge(&term.span());
+
+            // Negative numbers are kept as numbers
+            match (&operator, &term) {
+                (UnaryOperator::NEG, Self::INTEGER(number, _)) if *number != i64::MIN => { return Ok(Self::INTEGER(-number, span)) }
+                (UnaryOperator::NEG, Self::FLOAT(number, _))                          => { return Ok(Self::FLOAT(-number, span))   }
+                _ => { return Ok(Self::UNARY(operator, Box::new(term), span)) }
+            }
         }
-        else {
-            panic!("Math operator not found");
+
+        // A function call runs until the end of its arguments
+        let mut term_end = *position;
+        if string_equation.get(*position+1).is_some_and(|x| *x == syntax_elements.assignment_symbols["begin conditions"]) {
+            term_end = tokenizer.find_end_of_enclosure(string_equation, *position+1)?;
         }
+
+        let term = string_equation[*position..=term_end].to_vec();
+        *position = term_end + 1;
+
+        return Self::from_equation_term(tokenizer, variable_history, term)
     }
 
     /// Returns the data type this Assignment will become after evaluation
     pub fn evaluate_type(&self, variable_history: &VariableHistory) -> DataType { match self {
-        Self::INTEGER(_)   => { return DataType::INTEGER }
-        Self::FLOAT(_)     => { return DataType::FLOAT   }
-        Self::BOOL(_)      => { return DataType::BOOL    }
-        Self::CMP(_, _, _) => { return DataType::BOOL    }
+        Self::INTEGER(_, _)     => { return DataType::INTEGER }
+        Self::FLOAT(_, _)       => { return DataType::FLOAT   }
+        Self::BOOL(_, _)        => { return DataType::BOOL    }
+        Self::CMP(_, _, _, _)   => { return DataType::BOOL    }
+        Self::LOGIC(_, _, _, _) => { return DataType::BOOL    }
+
+        Self::UNARY(UnaryOperator::NEG, term, _)  => { return term.evaluate_type(variable_history) }
+        Self::UNARY(UnaryOperator::BNOT, term, _) => { return term.evaluate_type(variable_history) }
+        Self::UNARY(UnaryOperator::NOT, _, _)     => { return DataType::BOOL }
+
+        Self::CAST(_, data_type, _) => { return data_type.clone() }
 
-        Self::VAR(variable_location) => {
+        Self::VAR(variable_location, _) => {
             let variable = variable_history.data[*variable_location].clone().unwrap();
 
             return variable.data_type
         }
 
-        Self::FUNC(_, data_type, _) => {
+        Self::FUNC(_, data_type, _, _) => {
             return data_type.clone()
         }
 
-        Self::EVAL(first_term, _, second_term) => {
+        Self::EVAL(first_term, _, second_term, _) => {
             let first_term_type  = first_term.evaluate_type(variable_history);
             let second_term_type = second_term.evaluate_type(variable_history);
 
@@ -173,85 +521,165 @@ pub enum Assignment {
         }
     }}
 
+    /// Returns the location of every variable used anywhere in this Assignment
     pub fn get_all_vars_used(&self) -> Vec<usize> { match self {
-        Self::EVAL(first_assignment, _, second_assignment) => {
-            let mut result: Vec<usize> = Vec::new();
+        Self::EVAL(first_assignment, _, second_assignment, _) => {
+            return [first_assignment.get_all_vars_used(), second_assignment.get_all_vars_used()].concat()
+        }
+        Self::CMP(first_assignment, _, second_assignment, _) => {
+            return [first_assignment.get_all_vars_used(), second_assignment.get_all_vars_used()].concat()
+        }
+        Self::LOGIC(first_assignment, _, second_assignment, _) => {
+            return [first_assignment.get_all_vars_used(), second_assignment.get_all_vars_used()].concat()
+        }
+        Self::UNARY(_, assignment, _) => {
+            return assignment.get_all_vars_used()
+        }
+        Self::CAST(assignment, _, _) => {
+            return assignment.get_all_vars_used()
+        }
+        Self::FUNC(_, _, arguments, _) => {
+            return arguments.iter().flat_map(|x| x.get_all_vars_used()).collect()
+        }
+        Self::VAR(location, _) => {
+            return vec![*location]
+        }
+        _ => { return Vec::new() }
+    }}
 
-            if let Self::VAR(location) = *first_assignment.clone() {
-                result.push(location);
-            }
-            if let Self::VAR(location) = *second_assignment.clone() {
-                result.push(location);
-            }
+    /// Returns the span of the source text this Assignment was parsed from
+    pub fn span(&self) -> Span { match self {
+        Self::EVAL(_, _, _, span)  => { return *span }
+        Self::CMP(_, _, _, span)   => { return *span }
+        Self::LOGIC(_, _, _, span) => { return *span }
+        Self::UNARY(_, _, span)    => { return *span }
+        Self::CAST(_, _, span)     => { return *span }
+        Self::FUNC(_, _, _, span)  => { return *span }
+        Self::BOOL(_, span)        => { return *span }
+        Self::VAR(_, span)         => { return *span }
+        Self::INTEGER(_, span)     => { return *span }
+        Self::FLOAT(_, span)       => { return *span }
+    }}
 
-            return result;
+    /// Converts this Assignment into `data_type`, where `span` is the span of the whole conversion
+    ///
+    /// Whether this Assignment can actually become `data_type` is left to the type checker
+    pub fn convert_to(self, data_type: &DataType, variable_history: &VariableHistory, span: Span) -> Self {
+        if self.evaluate_type(variable_history) == *data_type {
+            return self.with_span(span)
         }
-        Self::CMP(first_assignment, _, second_assignment) => {
-            let mut result: Vec<usize> = Vec::new();
 
-            if let Self::VAR(location) = *first_assignment.clone() {
-                result.push(location);
-            }
-            if let Self::VAR(location) = *second_assignment.clone() {
-                result.push(location);
-            }
+        return Self::CAST(Box::new(self), data_type.clone(), span)
+    }
 
-            return result;
+    /// Implicitly converts this Assignment so it can be used where a `data_type` is expected
+    ///
+    /// Only an `int` is promoted (to a `flt`), anything else is left for the type checker
+    pub fn promote_to(self, data_type: &DataType, variable_history: &VariableHistory) -> Self {
+        if self.evaluate_type(variable_history).is_integer() && data_type.is_float() {
+            let span = self.span();
+            return self.convert_to(data_type, variable_history, span)
         }
-        _ => { return Vec::new() }
-    }}
+
+        return self
+    }
+
+    /// Returns this Assignment with its span replaced by `span`
+    pub fn with_span(mut self, span: Span) -> Self {
+        match &mut self {
+            Self::EVAL(_, _, _, old_span)  => { *old_span = span }
+            Self::CMP(_, _, _, old_span)   => { *old_span = span }
+            Self::LOGIC(_, _, _, old_span) => { *old_span = span }
+            Self::UNARY(_, _, old_span)    => { *old_span = span }
+            Self::CAST(_, _, old_span)     => { *old_span = span }
+            Self::FUNC(_, _, _, old_span)  => { *old_span = span }
+            Self::BOOL(_, old_span)        => { *old_span = span }
+            Self::VAR(_, old_span)         => { *old_span = span }
+            Self::INTEGER(_, old_span)     => { *old_span = span }
+            Self::FLOAT(_, old_span)       => { *old_span = span }
+        }
+
+        return self
+    }
+
+    /// Whether the word is written as an integer (only digits, without a `.`)
+    fn is_integer_literal(word: &Word) -> bool {
+        return !word.text.is_empty() && word.text.chars().all(|x| x.is_ascii_digit())
+    }
 
     /// Parses a singular term in an equation (functions, numbers) into Self
-    fn from_equation_term(tokenizer: &Tokenizer, variable_history: &VariableHistory, term: Vec<String>) -> Result<Self, TokenizerError> {
+    fn from_equation_term(tokenizer: &Tokenizer, variable_history: &VariableHistory, term: Vec<Word>) -> Result<Self, TokenizerError> {
         println!("coding_language::tokenizer::enumerators::Assignment::from_equation_term()");
         println!("  recieved: {term:?}");
 
         let syntax_elements = SyntaxElements::init();
 
         let begin_args_char = syntax_elements.assignment_symbols.get("begin conditions").unwrap();
-        let end_args_char   = syntax_elements.assignment_symbols.get("end conditions").unwrap();
+
+        let span = term.span();
 
         // Check if the declaration is an integer
-        if let Ok(returned_number) = term[0].clone().parse::<i64>() {
-            return Ok(Assignment::INTEGER(returned_number))
+        if let Ok(returned_number) = term[0].parse::<i64>() {
+            return Ok(Assignment::INTEGER(returned_number, span))
+        }
+        // Numbers without a `.` are never turned into a float when they don't fit in an integer
+        else if Self::is_integer_literal(&term[0]) {
+            return Err(TokenizerError::IntegerOutOfRange { span })
         }
         // Check if the declaration is a float
-        else if let Ok(returned_number) = term[0].clone().parse::<f64>() {
-            return Ok(Assignment::FLOAT(returned_number))
+        else if let Ok(returned_number) = term[0].parse::<f64>() {
+            return Ok(Assignment::FLOAT(returned_number, span))
         }
         // Chech if the declaration is a boolean value
-        else if syntax_elements.comparision_names.contains_value(&term[0]) {
+        else if syntax_elements.comparision_names.contains_value(&term[0].text) {
             if term[0] == syntax_elements.comparision_names["true"] {
-                return Ok(Assignment::BOOL(1))
+                return Ok(Assignment::BOOL(1, span))
             } else if term[0] == syntax_elements.comparision_names["false"] {
-                return Ok(Assignment::BOOL(0))
+                return Ok(Assignment::BOOL(0, span))
             } else {
-                return Err(TokenizerError::CouldNotParseTerm)
+                return Err(TokenizerError::CouldNotParseTerm { term: term[0].text.clone(), span })
             }
         }
         // Check if the declaration is a variable
         else if let Some(variable_location_index) = variable_history.find_variable(&term[0]) {
-            return Ok(Assignment::VAR(variable_location_index))
+            return Ok(Assignment::VAR(variable_location_index, span))
         }
         // Check if the declaration is a function
         else if let Some(function) = tokenizer.function_history.find_by_name(&term[0]) {
             // Find the begin and end args characters
-            let begin_args_index = term.find(begin_args_char).unwrap();
-            let end_args_index   = term.find(end_args_char).unwrap();
+            let begin_args_index = term.find(begin_args_char)
+                .ok_or(TokenizerError::MissingSymbol { expected: begin_args_char.clone(), span })?;
+            let end_args_index   = tokenizer.find_end_of_enclosure(&term, begin_args_index)?;
 
             // Get the argument slice
-            let passed_args_slice: Vec<String> = term[begin_args_index+1..=end_args_index-1].to_vec();
+            let passed_args_slice: Vec<Word> = term[begin_args_index+1..end_args_index].to_vec();
             
             // Get the name of the function and make a list of all the arguments
             let mut passed_args: Vec<Assignment> = Vec::new();
-            if passed_args_slice.len() > 0 { for passed_argument_string in passed_args_slice.split(|x| x==",").into_iter() {
-                passed_args.push(Self::from_equation_term(tokenizer, variable_history, passed_argument_string.to_vec()).unwrap())
+            if !passed_args_slice.is_empty() { for (argument_index, passed_argument_string) in tokenizer.split_enclosure_contents(&passed_args_slice).into_iter().enumerate() {
+                if passed_argument_string.is_empty() {
+                    return Err(TokenizerError::MissingValue { span: passed_args_slice.span() })
+                }
+
+                let mut passed_argument = Self::from_string_vec(tokenizer, variable_history, passed_argument_string.to_vec())?;
+                if let Some(argument) = function.arguments.get(argument_index) {
+                    passed_argument = passed_argument.promote_to(&argument.data_type, variable_history);
+                }
+
+                passed_args.push(passed_argument)
             }};
 
-            return Ok(Assignment::FUNC(function.name, function.return_type, passed_args));
+            return Ok(Assignment::FUNC(function.name, function.return_type, passed_args, span));
+        }
+        // Otherwise it's a name that was never declared (or is out of scope)
+        else if term.get(1).is_some_and(|x| x == begin_args_char) {
+            Err(TokenizerError::UndeclaredFunction { name: term[0].text.clone(), span: term[0].span })
+        }
+        else if term[0].starts_with(|x: char| x.is_alphabetic() || x == '_') {
+            Err(variable_history.not_found(&term[0].text, term[0].span))
         }
         else {
-            Err(TokenizerError::CouldNotParseTerm)
+            Err(TokenizerError::CouldNotParseTerm { term: term[0].text.clone(), span: term[0].span })
         }
     }
 }
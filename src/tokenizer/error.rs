@@ -1,27 +1,208 @@
 use thiserror::Error;
 
+use crate::diagnostics::{Diagnostic, ToDiagnostic};
+use crate::optimizer::span::Span;
+
 
 #[derive(Error, Debug)]
 pub enum TokenizerError {
-    #[error("The stack_data variable in a Memory structure is incorrectly formatted.
-             Ensure stack_data always ends with a None construct")]
+    #[error("The stack_data variable in a Memory structure is incorrectly formatted")]
     IncorrectStackDataFormatting,
-    #[error("The equation inserted into Assignment::from_string_vec() was incorrectly formatted.
-             Ensure you are using proper built-in arithmetic characters in the equation")]
-    IncorrectEquationFormatting,
-    #[error("Assignment could not parse this term in a variable declaration")]
-    CouldNotParseTerm,
+    #[error("The equation inserted into Assignment::from_string_vec() was incorrectly formatted")]
+    IncorrectEquationFormatting { span: Span },
+    #[error("Assignment could not parse `{term}` in this equation")]
+    CouldNotParseTerm { term: String, span: Span },
     #[error("Operator could not parse this math operator")]
     CouldNotParseMathOperator,
     #[error("Operator could not parse this comparison operator")]
     CouldNotParseComparisonOperator,
-    #[error("Tokenizer could not find the end of this block.
-             Ensure you properly ended this block or didn't forget any miscellanious end block characters")]
-    CouldNotFindEndOfBlock,
-    #[error("A non-condition assignment was used to construct this conditional statement.
-             Ensure you used a conditional statement or a tof value")]
+    #[error("Tokenizer could not find the end of this block")]
+    CouldNotFindEndOfBlock { span: Span },
+    #[error("A non-condition assignment was used to construct this conditional statement")]
     IncorrectAssignmentForConditionalCondition,
-    #[error("A variable was not passed through a cmp and was used in one of the branches.
-             Ensure you have passed the variable between the :&[]")]
-    VarNotUsedInComparison,
+    #[error("The variable `{name}` was not passed through a cmpr and was used in one of the branches")]
+    VarNotUsedInComparison { name: String, span: Span },
+    #[error("unknown type `{name}`")]
+    UnknownType { name: String, span: Span },
+    #[error("expected a type")]
+    MissingType { span: Span },
+    #[error("expected a name")]
+    MissingName { span: Span },
+    #[error("expected a value")]
+    MissingValue { span: Span },
+    #[error("expected `{expected}`")]
+    MissingSymbol { expected: String, span: Span },
+    #[error("this statement is never ended")]
+    MissingEndAssignment { span: Span },
+    #[error("use of undeclared variable `{name}`")]
+    UndeclaredVariable { name: String, span: Span },
+    #[error("call to undeclared subroutine `{name}`")]
+    UndeclaredFunction { name: String, span: Span },
+    #[error("the subroutine `{name}` has no return type")]
+    MissingReturnArrow { name: String, span: Span },
+    #[error("expected an operator, found `{word}`")]
+    UnexpectedWord { word: String, span: Span },
+    #[error("this `(` is never closed")]
+    UnclosedGroup { span: Span },
+    #[error("`{keyword}` outside of a loop")]
+    LoopControlOutsideLoop { keyword: String, span: Span },
+    #[error("use of undeclared loop label `{label}`")]
+    UndeclaredLoopLabel { label: String, span: Span },
+    #[error("use of out of scope variable `{name}`")]
+    VariableOutOfScope { name: String, span: Span },
+    #[error("the variable `{name}` is declared twice in the same block")]
+    RedeclaredVariable { name: String, span: Span, previous_span: Span },
+    #[error("the subroutine `{name}` is declared twice")]
+    DuplicateFunction { name: String, span: Span, previous_span: Span },
+    #[error("the argument `{name}` is declared twice in the same subroutine")]
+    DuplicateArgument { name: String, span: Span, previous_span: Span },
+    #[error("expected a statement, found `{word}`")]
+    UnknownStatement { word: String, span: Span },
+    #[error("integer literal out of range")]
+    IntegerOutOfRange { span: Span },
+    #[error("expected `subroutine`, found `{word}`")]
+    ExpectedSubroutine { word: String, span: Span },
+} impl TokenizerError {
+    /// The error code shown alongside this error when it's rendered
+    pub fn code(&self) -> &'static str { match self {
+        Self::IncorrectStackDataFormatting                  => { "E0100" }
+        Self::IncorrectEquationFormatting { .. }            => { "E0101" }
+        Self::CouldNotParseTerm { .. }                      => { "E0102" }
+        Self::CouldNotParseMathOperator                     => { "E0103" }
+        Self::CouldNotParseComparisonOperator               => { "E0104" }
+        Self::CouldNotFindEndOfBlock { .. }                 => { "E0105" }
+        Self::IncorrectAssignmentForConditionalCondition    => { "E0106" }
+        Self::VarNotUsedInComparison { .. }                 => { "E0107" }
+        Self::UnknownType { .. }                            => { "E0108" }
+        Self::MissingType { .. }                            => { "E0109" }
+        Self::MissingName { .. }                            => { "E0110" }
+        Self::MissingValue { .. }                           => { "E0111" }
+        Self::MissingSymbol { .. }                          => { "E0112" }
+        Self::MissingEndAssignment { .. }                   => { "E0113" }
+        Self::UndeclaredVariable { .. }                     => { "E0114" }
+        Self::UndeclaredFunction { .. }                     => { "E0115" }
+        Self::MissingReturnArrow { .. }                     => { "E0116" }
+        Self::UnexpectedWord { .. }                         => { "E0117" }
+        Self::UnclosedGroup { .. }                          => { "E0118" }
+        Self::LoopControlOutsideLoop { .. }                 => { "E0121" }
+        Self::UndeclaredLoopLabel { .. }                    => { "E0122" }
+        Self::VariableOutOfScope { .. }                     => { "E0123" }
+        Self::RedeclaredVariable { .. }                     => { "E0124" }
+        Self::DuplicateFunction { .. }                      => { "E0125" }
+        Self::DuplicateArgument { .. }                      => { "E0126" }
+        Self::UnknownStatement { .. }                       => { "E0127" }
+        Self::IntegerOutOfRange { .. }                      => { "E0128" }
+        Self::ExpectedSubroutine { .. }                     => { "E0129" }
+    }}
+
+    /// The span of the source text that caused this error, if there is one
+    pub fn span(&self) -> Option<Span> { match self {
+        Self::IncorrectEquationFormatting { span }    => { Some(*span) }
+        Self::CouldNotParseTerm { span, .. }          => { Some(*span) }
+        Self::CouldNotFindEndOfBlock { span }         => { Some(*span) }
+        Self::VarNotUsedInComparison { span, .. }     => { Some(*span) }
+        Self::UnknownType { span, .. }                => { Some(*span) }
+        Self::MissingType { span }                    => { Some(*span) }
+        Self::MissingName { span }                    => { Some(*span) }
+        Self::MissingValue { span }                   => { Some(*span) }
+        Self::MissingSymbol { span, .. }              => { Some(*span) }
+        Self::MissingEndAssignment { span }           => { Some(*span) }
+        Self::UndeclaredVariable { span, .. }         => { Some(*span) }
+        Self::UndeclaredFunction { span, .. }         => { Some(*span) }
+        Self::MissingReturnArrow { span, .. }         => { Some(*span) }
+        Self::UnexpectedWord { span, .. }             => { Some(*span) }
+        Self::UnclosedGroup { span }                  => { Some(*span) }
+        Self::LoopControlOutsideLoop { span, .. }     => { Some(*span) }
+        Self::UndeclaredLoopLabel { span, .. }        => { Some(*span) }
+        Self::VariableOutOfScope { span, .. }         => { Some(*span) }
+        Self::RedeclaredVariable { span, .. }         => { Some(*span) }
+        Self::DuplicateFunction { span, .. }          => { Some(*span) }
+        Self::DuplicateArgument { span, .. }          => { Some(*span) }
+        Self::UnknownStatement { span, .. }           => { Some(*span) }
+        Self::IntegerOutOfRange { span }              => { Some(*span) }
+        Self::ExpectedSubroutine { span, .. }         => { Some(*span) }
+                                                    _ => { None }
+    }}
+
+    /// What to write under the span of this error
+    pub fn label(&self) -> String { match self {
+        Self::IncorrectEquationFormatting { .. } => { "this equation could not be understood".into() }
+        Self::CouldNotParseTerm { .. }           => { "not a number, tof value, variable or subroutine call".into() }
+        Self::CouldNotFindEndOfBlock { .. }      => { "this block is never closed with `;`".into() }
+        Self::VarNotUsedInComparison { .. }      => { "used here".into() }
+        Self::UnknownType { .. }                 => { "not a type".into() }
+        Self::MissingType { .. }                 => { "expected one of `int`, `flt` or `tof` here".into() }
+        Self::MissingName { .. }                 => { "expected a name here".into() }
+        Self::MissingValue { .. }                => { "expected a value after this".into() }
+        Self::MissingSymbol { expected, .. }     => { format!("expected `{expected}` in this statement") }
+        Self::MissingEndAssignment { .. }        => { "expected a new line after this".into() }
+        Self::UndeclaredVariable { .. }          => { "not found in this subroutine".into() }
+        Self::UndeclaredFunction { .. }          => { "no subroutine with this name was declared before this".into() }
+        Self::MissingReturnArrow { .. }          => { "expected `-> <type>` before the `:`".into() }
+        Self::UnexpectedWord { .. }              => { "expected an operator before this".into() }
+        Self::UnclosedGroup { .. }               => { "unclosed group".into() }
+        Self::LoopControlOutsideLoop { .. }      => { "not inside a `repeat` or `loop`".into() }
+        Self::UndeclaredLoopLabel { .. }         => { "no loop around this has this label".into() }
+        Self::VariableOutOfScope { .. }          => { "declared in a block that already ended".into() }
+        Self::RedeclaredVariable { name, .. }    => { format!("`{name}` declared again here") }
+        Self::DuplicateFunction { name, .. }     => { format!("`{name}` declared again here") }
+        Self::DuplicateArgument { name, .. }     => { format!("`{name}` declared again here") }
+        Self::UnknownStatement { .. }            => { "not a statement".into() }
+        Self::IntegerOutOfRange { .. }           => { "does not fit in an `int`".into() }
+        Self::ExpectedSubroutine { .. }          => { "not a subroutine".into() }
+                                               _ => { String::new() }
+    }}
+
+    /// A second span of source text to point at for context, and what to write under it
+    pub fn secondary_label(&self) -> Option<(Span, String)> { match self {
+        Self::RedeclaredVariable { name, previous_span, .. } => { Some((*previous_span, format!("`{name}` first declared here"))) }
+        Self::DuplicateFunction { name, previous_span, .. }  => { Some((*previous_span, format!("`{name}` first declared here"))) }
+        Self::DuplicateArgument { name, previous_span, .. }  => { Some((*previous_span, format!("`{name}` first declared here"))) }
+                                                           _ => { None }
+    }}
+
+    /// A suggestion on how to fix this error, if there is one
+    pub fn help(&self) -> Option<&'static str> { match self {
+        Self::IncorrectStackDataFormatting               => { Some("ensure stack_data always ends with a None construct") }
+        Self::IncorrectEquationFormatting { .. }         => { Some("ensure you are using proper built-in arithmetic characters in the equation") }
+        Self::CouldNotFindEndOfBlock { .. }              => { Some("ensure you properly ended this block with `;` or didn't forget any miscellanious end block characters") }
+        Self::IncorrectAssignmentForConditionalCondition => { Some("ensure you used a conditional statement or a tof value") }
+        Self::VarNotUsedInComparison { .. }              => { Some("ensure you have passed the variable between the `:&[` and `]`") }
+        Self::UnknownType { .. }                         => { Some("the available types are `int`, `flt` and `tof`") }
+        Self::MissingEndAssignment { .. }                => { Some("every statement has to end with a new line") }
+        Self::UndeclaredVariable { .. }                  => { Some("variables have to be declared with `decl` before they are used") }
+        Self::UndeclaredFunction { .. }                  => { Some("subroutines have to be declared above the code that calls them") }
+        Self::MissingReturnArrow { .. }                  => { Some("subroutines are declared as `subroutine <name>::[<arguments>] -> <type> :`") }
+        Self::UnclosedGroup { .. }                       => { Some("close the group with `)` before the end of the equation") }
+        Self::UndeclaredLoopLabel { .. }                 => { Some("a `repeat` is labelled by a name after it (`repeat outer :*[...]`) and a `loop` by its variable") }
+        Self::VariableOutOfScope { .. }                  => { Some("variables declared in a `cmpr` branch or a loop can only be used before its `;`, declare it before the block to use it after") }
+        Self::RedeclaredVariable { .. }                  => { Some("use `chng` to give the variable a new value, or give the new variable another name") }
+        Self::DuplicateFunction { .. }                   => { Some("every subroutine needs a name of its own") }
+        Self::DuplicateArgument { .. }                   => { Some("every argument of a subroutine needs a name of its own") }
+        Self::UnknownStatement { .. }                    => { Some("statements start with `decl`, `chng`, `expose`, `cmpr`, `repeat`, `loop`, `break` or `continue`") }
+        Self::IntegerOutOfRange { .. }                   => { Some("an `int` goes from -9223372036854775808 to 9223372036854775807, write the number with a `.` to make it a `flt`") }
+        Self::ExpectedSubroutine { .. }                  => { Some("only subroutines can be declared outside of a subroutine, as `subroutine <name>::[<arguments>] -> <type> :`") }
+                                                       _ => { None }
+    }}
+}
+
+impl ToDiagnostic for TokenizerError {
+    fn to_diagnostic(&self) -> Diagnostic {
+        let mut diagnostic = Diagnostic::error(&self.to_string())
+            .with_code(self.code());
+
+        if let Some(span) = self.span() {
+            diagnostic = diagnostic.with_label(span, &self.label());
+        }
+
+        if let Some((span, label)) = self.secondary_label() {
+            diagnostic = diagnostic.with_secondary_label(span, &label);
+        }
+
+        if let Some(help) = self.help() {
+            diagnostic = diagnostic.with_help(help);
+        }
+
+        return diagnostic
+    }
 }
@@ -2,6 +2,7 @@ use super::enumerators::Assignment;
 use super::declaration::DataType;
 use super::structures::{VariableHistory, Variable};
 use super::Token;
+use crate::optimizer::span::Span;
 
 
 #[derive(Debug, Clone)]
@@ -11,9 +12,11 @@ pub struct Function {
     pub arguments: Vec<Variable>,
     pub variable_history: VariableHistory,
     pub functionaliy: Vec<Token>,
+    pub span: Span,
 }
 
 #[derive(Debug, Clone)]
 pub struct Return {
     pub assignment: Assignment,
+    pub span: Span,
 }
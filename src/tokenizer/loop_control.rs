@@ -0,0 +1,17 @@
+use crate::optimizer::span::Span;
+
+
+/// Leaves the loop labelled `label` (or the innermost loop if there's no label)
+#[derive(Debug, Clone)]
+pub struct Break {
+    pub label: Option<String>,
+    pub span: Span,
+}
+
+/// Skips to the next pass of the loop labelled `label` (or the innermost loop if there's no
+/// label)
+#[derive(Debug, Clone)]
+pub struct Continue {
+    pub label: Option<String>,
+    pub span: Span,
+}
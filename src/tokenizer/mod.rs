@@ -1,5 +1,6 @@
 use crate::type_traits::vector::VecExtra;
 use crate::data::{SyntaxElements, MEMORY_STEP};
+use crate::optimizer::span::{Span, Word, WordSliceExtra};
 
 
 #[allow(dead_code)]
@@ -28,7 +29,7 @@ pub mod enumerators;
     use enumerators::Assignment;
 
 #[allow(dead_code)]
-mod error;
+pub mod error;
     use error::TokenizerError;
 
 #[allow(dead_code)]
@@ -39,41 +40,85 @@ pub mod reassignment;
 pub mod conditional_statement;
     use conditional_statement::ConditionalStatement;
 
+#[allow(dead_code)]
+pub mod loop_control;
+    use loop_control::{Break, Continue};
+
 
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
 pub enum Token {
     ConditionalLoop(ConditionalLoop),
     Function(Function),
-    TerminatingLoop(TerminatingLoop),
+    TerminatingLoop(Box<TerminatingLoop>),
     ConditionalStatement(ConditionalStatement),
     Declaration(Declaration),
     Reassignment(Reassignment),
     Return(Return),
+    Break(Break),
+    Continue(Continue),
+} impl Token {
+    #[allow(dead_code)]
+    /// Returns the span of the source text this token was parsed from
+    pub fn span(&self) -> Span { match self {
+        Self::ConditionalLoop(conditional_loop)           => { return conditional_loop.span      }
+        Self::Function(function)                          => { return function.span              }
+        Self::TerminatingLoop(terminating_loop)           => { return terminating_loop.span      }
+        Self::ConditionalStatement(conditional_statement) => { return conditional_statement.span }
+        Self::Declaration(declaration)                    => { return declaration.span           }
+        Self::Reassignment(reassignment)                  => { return reassignment.span          }
+        Self::Return(return_statement)                    => { return return_statement.span      }
+        Self::Break(break_statement)                      => { return break_statement.span       }
+        Self::Continue(continue_statement)                => { return continue_statement.span    }
+    }}
 }
 
 
 pub struct Tokenizer {
     pub token_tree: Vec<Token>,
 
+    errors: Vec<TokenizerError>,
     function_history: FunctionHistory,
     syntax_elements: SyntaxElements,
+    /// The label of every loop around the statement being tokenized, innermost last
+    enclosing_loops: Vec<Option<String>>,
 
 } impl Tokenizer {
     pub fn init() -> Self { Self {
         token_tree: Vec::new(),
 
+        errors: Vec::new(),
         function_history: FunctionHistory::init(),
         syntax_elements: SyntaxElements::init(),
+        enclosing_loops: Vec::new(),
     }}
 
-    pub fn create_token_tree(&mut self, optimized_file_content: &Vec<String>) {
-        let token_tree = self.generate_token_tree(&mut None, optimized_file_content);
+    /// Tokenizes the whole file into `token_tree`
+    ///
+    /// Returns Err() with every error found (in the order they appear in the file) if any
+    /// statement could not be tokenized
+    pub fn create_token_tree(&mut self, optimized_file_content: &Vec<Word>) -> Result<(), Vec<TokenizerError>> {
+        self.errors.clear();
 
+        let token_tree = self.generate_token_tree(&mut None, optimized_file_content);
         self.token_tree = token_tree;
+
+        if !self.errors.is_empty() {
+            let mut errors = std::mem::take(&mut self.errors);
+            errors.sort_by_key(|x| x.span().map(|span| (span.file_id, span.start)));
+
+            return Err(errors)
+        }
+
+        return Ok(())
     }
 
-    pub fn generate_token_tree(&mut self, parent_ref: &mut Option<&mut Function>, content_to_tokenize: &Vec<String>) -> Vec<Token> {
+    /// Tokenizes `content_to_tokenize` into a list of tokens
+    ///
+    /// Statements that fail to tokenize are skipped (up to their end assignment character or
+    /// the end of their block) and their error is recorded, so every error in the file can be
+    /// reported at once
+    pub fn generate_token_tree(&mut self, parent_ref: &mut Option<&mut Function>, content_to_tokenize: &Vec<Word>) -> Vec<Token> {
         let mut result: Vec<Token> = Vec::new();
 
         let mut i: usize = 0;
@@ -82,19 +127,35 @@ pub struct Tokenizer {
 
             // Declarion handling
             match &current_word {
+                // Only subroutines can be declared outside of a subroutine
+                val if parent_ref.is_none() && *val != self.syntax_elements.declaration_names["function"] && !self.syntax_elements.get_all_symbols().contains(&val.text) => {
+                    self.errors.push(TokenizerError::ExpectedSubroutine { word: val.text.clone(), span: val.span });
+
+                    // Skip past the line, and the body it opens if it has one
+                    i = self.find_end_of_stray_statement(content_to_tokenize, i);
+                    continue;
+                }
+
                 val if *val == self.syntax_elements.declaration_names["variable"] => { if let Some(parent) = parent_ref {
                     // Get the first instance of the end assignment character after the
                     // declaration (therefore ending it)
-                    let declaration_stop_char = self.syntax_elements.assignment_symbols.get("end assignment").unwrap();
-                    let declaration_stop_index = content_to_tokenize.find_after_index(i, declaration_stop_char).unwrap();
+                    // Nothing after a statement that is never ended can be recovered
+                    let Some(declaration_stop_index) = self.recover(self.find_end_of_assignment(content_to_tokenize, i)) else { break };
 
                     // Get the slice from this index (the declaration start) to the end
                     // assignment char (the declaration end)
                     let declaration_to_evaluate = content_to_tokenize[i..declaration_stop_index].to_vec();
 
                     // Parse the slice into a token and add it to the result
-                    let created_token = self.parse_variable(&mut parent.variable_history, declaration_to_evaluate);
-                    result.push(created_token);
+                    // If it fails, still declare the variable (if possible) so later uses of it
+                    // don't report errors of their own
+                    match self.parse_variable(&mut parent.variable_history, declaration_to_evaluate.clone()) {
+                        Ok(created_token) => { result.push(created_token) }
+                        Err(error)        => {
+                            self.errors.push(error);
+                            self.recover_declaration(&mut parent.variable_history, &declaration_to_evaluate);
+                        }
+                    }
 
                     // Move the current word to one word after the end of this declaration and
                     // continue the loop
@@ -105,16 +166,16 @@ pub struct Tokenizer {
                 val if *val == self.syntax_elements.declaration_names["reassignment"] => { if let Some(parent) = parent_ref {
                     // Get the first instance of the end assignment character after the
                     // declaration (therefore ending it)
-                    let declaration_stop_char = self.syntax_elements.assignment_symbols.get("end assignment").unwrap();
-                    let declaration_stop_index = content_to_tokenize.find_after_index(i, declaration_stop_char).unwrap();
+                    let Some(declaration_stop_index) = self.recover(self.find_end_of_assignment(content_to_tokenize, i)) else { break };
 
                     // Get the slice from this index (the declaration start) to the end
                     // assignment char (the declaration end)
                     let declaration_to_evaluate = content_to_tokenize[i..declaration_stop_index].to_vec();
 
                     // Parse the slice into a token and add it to the result
-                    let created_token = self.parse_reassignment(&parent.variable_history, declaration_to_evaluate);
-                    result.push(created_token);
+                    if let Some(created_token) = self.recover(self.parse_reassignment(&parent.variable_history, declaration_to_evaluate)) {
+                        result.push(created_token);
+                    }
 
                     // Move the current word to one word after the end of this declaration and
                     // continue the loop
@@ -125,20 +186,27 @@ pub struct Tokenizer {
                 val if *val == self.syntax_elements.declaration_names["function"] => {
                     // Get the first instance of the end block character after the
                     // declaration (therefore ending it)
-                    let block_start_char = self.syntax_elements.assignment_symbols.get("begin body").unwrap();
-                    let block_start_index = content_to_tokenize.find_after_index(i, block_start_char).unwrap();
-                    let declaration_stop_index = self.find_end_of_block(&content_to_tokenize, block_start_index).unwrap();
+                    // If the function has no body, skip its header
+                    let Some(block_start_index) = self.recover(self.find_symbol(content_to_tokenize, i, "begin body")) else {
+                        i = self.find_end_of_assignment(content_to_tokenize, i).unwrap_or(content_to_tokenize.len());
+                        continue;
+                    };
+                    // Nothing after a block that is never closed can be recovered
+                    let Some(declaration_stop_index) = self.recover(self.find_end_of_block(&content_to_tokenize, block_start_index)) else { break };
 
                     // Get the slice from this index (the declaration start) to the
                     // block char (the declaration end)
                     let declaration_to_evaluate = content_to_tokenize[i..=declaration_stop_index].to_vec();
 
-                    // Parse the slice into a token and add it to the result
+                    // Parse the slice into a token and add it to the result, unless a function
+                    // with the same name was already added
                     let created_token = self.parse_function(declaration_to_evaluate);
-                    if let Token::Function(function) = created_token.clone() {
-                        self.function_history.add_function(function);
+                    if let Some(Token::Function(function)) = self.recover(created_token) {
+                        let added = self.function_history.add_function(function.clone());
+                        if self.recover(added).is_some() {
+                            result.push(Token::Function(function));
+                        }
                     }
-                    result.push(created_token);
 
                     // Move the current word to one word after the end of this declaration and
                     // continue the loop
@@ -147,19 +215,20 @@ pub struct Tokenizer {
                 }
 
                 val if *val == self.syntax_elements.declaration_names["conditional statement"] => { if let Some(parent) = parent_ref.as_mut() {
-                    // Get necessary chars
-                    let block_start_char = self.syntax_elements.assignment_symbols["begin body"].clone();
-
                     // Get the index of the chars
-                    let block_start_index = content_to_tokenize.find_after_index(i, &block_start_char).unwrap();
+                    let Some(block_start_index) = self.recover(self.find_symbol(content_to_tokenize, i, "begin body")) else {
+                        i = self.find_end_of_assignment(content_to_tokenize, i).unwrap_or(content_to_tokenize.len());
+                        continue;
+                    };
 
                     // Get the index of the end of this comparison
-                    let declaration_stop_index = self.find_end_of_block(content_to_tokenize, block_start_index).unwrap();
+                    let Some(declaration_stop_index) = self.recover(self.find_end_of_block(content_to_tokenize, block_start_index)) else { break };
 
                     // Parse the slice into a token and add it to the result
-                    let created_token = self.parse_conditional_statement(parent, content_to_tokenize[i..=declaration_stop_index].to_vec())
-                        .unwrap();
-                    result.push(created_token);
+                    let created_token = self.parse_conditional_statement(parent, content_to_tokenize[i..=declaration_stop_index].to_vec());
+                    if let Some(created_token) = self.recover(created_token) {
+                        result.push(created_token);
+                    }
 
                     // Move the current word to one word after the end of this declaration and
                     // continue the loop
@@ -168,18 +237,42 @@ pub struct Tokenizer {
                 }}
 
                 val if *val == self.syntax_elements.declaration_names["conditional loop"] => { if let Some(parent) = parent_ref.as_mut() {
-                    // Get necessary chars
-                    let block_start_char = self.syntax_elements.assignment_symbols["begin body"].clone();
-
                     // Get the index of the chars
-                    let block_start_index = content_to_tokenize.find_after_index(i, &block_start_char).unwrap();
+                    let Some(block_start_index) = self.recover(self.find_symbol(content_to_tokenize, i, "begin body")) else {
+                        i = self.find_end_of_assignment(content_to_tokenize, i).unwrap_or(content_to_tokenize.len());
+                        continue;
+                    };
 
                     // Get the index of the end of this comparison
-                    let declaration_stop_index = self.find_end_of_block(content_to_tokenize, block_start_index).unwrap();
+                    let Some(declaration_stop_index) = self.recover(self.find_end_of_block(content_to_tokenize, block_start_index)) else { break };
 
                     // Parse the slice into a token and add it to the result
                     let created_token = self.parse_conditional_loop(parent, content_to_tokenize[i..=declaration_stop_index].to_vec());
-                    result.push(created_token);
+                    if let Some(created_token) = self.recover(created_token) {
+                        result.push(created_token);
+                    }
+
+                    // Move the current word to one word after the end of this declaration and
+                    // continue the loop
+                    i = declaration_stop_index;
+                    continue;
+                }}
+
+                val if *val == self.syntax_elements.declaration_names["terminating loop"] => { if let Some(parent) = parent_ref.as_mut() {
+                    // Get the index of the chars
+                    let Some(block_start_index) = self.recover(self.find_symbol(content_to_tokenize, i, "begin body")) else {
+                        i = self.find_end_of_assignment(content_to_tokenize, i).unwrap_or(content_to_tokenize.len());
+                        continue;
+                    };
+
+                    // Get the index of the end of this loop
+                    let Some(declaration_stop_index) = self.recover(self.find_end_of_block(content_to_tokenize, block_start_index)) else { break };
+
+                    // Parse the slice into a token and add it to the result
+                    let created_token = self.parse_terminating_loop(parent, content_to_tokenize[i..=declaration_stop_index].to_vec());
+                    if let Some(created_token) = self.recover(created_token) {
+                        result.push(created_token);
+                    }
 
                     // Move the current word to one word after the end of this declaration and
                     // continue the loop
@@ -187,19 +280,51 @@ pub struct Tokenizer {
                     continue;
                 }}
 
+                val if *val == self.syntax_elements.declaration_names["break"] && parent_ref.is_some() => {
+                    // Get the first instance of the end assignment character after the
+                    // statement (therefore ending it)
+                    let Some(declaration_stop_index) = self.recover(self.find_end_of_assignment(content_to_tokenize, i)) else { break };
+
+                    // Parse the slice into a token and add it to the result
+                    if let Some(created_token) = self.recover(self.parse_break(content_to_tokenize[i..declaration_stop_index].to_vec())) {
+                        result.push(created_token);
+                    }
+
+                    // Move the current word to one word after the end of this statement and
+                    // continue the loop
+                    i = declaration_stop_index;
+                    continue;
+                }
+
+                val if *val == self.syntax_elements.declaration_names["continue"] && parent_ref.is_some() => {
+                    // Get the first instance of the end assignment character after the
+                    // statement (therefore ending it)
+                    let Some(declaration_stop_index) = self.recover(self.find_end_of_assignment(content_to_tokenize, i)) else { break };
+
+                    // Parse the slice into a token and add it to the result
+                    if let Some(created_token) = self.recover(self.parse_continue(content_to_tokenize[i..declaration_stop_index].to_vec())) {
+                        result.push(created_token);
+                    }
+
+                    // Move the current word to one word after the end of this statement and
+                    // continue the loop
+                    i = declaration_stop_index;
+                    continue;
+                }
+
                 val if *val == self.syntax_elements.declaration_names["return"] => { if let Some(parent) = parent_ref.as_mut() {
                     // Get the first instance of the end block character after the
                     // declaration (therefore ending it)
-                    let block_stop_char = self.syntax_elements.assignment_symbols.get("end assignment").unwrap();
-                    let declaration_stop_index = content_to_tokenize.find_after_index(i, &block_stop_char).unwrap();
+                    let Some(declaration_stop_index) = self.recover(self.find_end_of_assignment(content_to_tokenize, i)) else { break };
 
                     // Get the slice from this index (the declaration start) to the
                     // block char (the declaration end)
-                    let declaration_to_evaluate = content_to_tokenize[i..=declaration_stop_index-1].to_vec();
+                    let declaration_to_evaluate = content_to_tokenize[i..declaration_stop_index].to_vec();
 
                     // Parse the slice into a token and add it to the result
-                    let created_token = self.parse_return(parent, declaration_to_evaluate);
-                    result.push(created_token);
+                    if let Some(created_token) = self.recover(self.parse_return(parent, declaration_to_evaluate)) {
+                        result.push(created_token);
+                    }
 
                     // Move the current word to one word after the end of this declaration and
                     // continue the loop
@@ -207,6 +332,16 @@ pub struct Tokenizer {
                     continue;
                 }}
 
+                // Anything else starting a statement in a body is not a statement
+                val if parent_ref.is_some() && !self.syntax_elements.get_all_symbols().contains(&val.text) => {
+                    self.errors.push(TokenizerError::UnknownStatement { word: val.text.clone(), span: val.span });
+
+                    // Skip to the end of the statement
+                    let Some(declaration_stop_index) = self.recover(self.find_end_of_assignment(content_to_tokenize, i)) else { break };
+                    i = declaration_stop_index;
+                    continue;
+                }
+
                 _ => {}
             }
 
@@ -216,13 +351,32 @@ pub struct Tokenizer {
         return result
     }
 
+    /// Records the error in `result` (if there is one) so tokenizing can continue
+    fn recover<T>(&mut self, result: Result<T, TokenizerError>) -> Option<T> { match result {
+        Ok(value)  => { return Some(value) }
+        Err(error) => { self.errors.push(error); return None }
+    }}
+
+    /// Adds the variable a failed declaration was trying to declare (if its name and type can be
+    /// parsed) so that later uses of it don't report errors of their own
+    fn recover_declaration(&self, variable_history: &mut VariableHistory, declaration: &Vec<Word>) {
+        let Ok(name) = self.parse_name(declaration) else { return };
+        let Ok(begin_set_type_index) = self.find_symbol(declaration, 1, "begin set type") else { return };
+        let Ok(end_set_type_index) = self.find_end_of_enclosure(declaration, begin_set_type_index) else { return };
+        let Ok(data_type) = self.parse_data_type(&declaration[begin_set_type_index..=end_set_type_index]) else { return };
+
+        if variable_history.find_variable(&name).is_none() {
+            let _ = variable_history.add_variable(Variable::new(&name, data_type, declaration[1].span));
+        }
+    }
+
     /// Finds the index of the end of a code block given the start of said block
     ///
     /// This is usefull for finding the end of a function containing blocks within itself (if
     /// statements, for example)
     ///
     /// Note that you should place the start_index at the beginning of the block
-    fn find_end_of_block(&self, content: &[String], start_index: usize) -> Result<usize, TokenizerError> {
+    fn find_end_of_block(&self, content: &[Word], start_index: usize) -> Result<usize, TokenizerError> {
         // Keep track of the end_of_block_index
         let mut end_of_block_index: Option<usize> = None;
 
@@ -252,95 +406,359 @@ pub struct Tokenizer {
         // Otherwise, return an error
         match end_of_block_index {
             Some(index) => { return Ok(index) }
-            None        => { return Err(TokenizerError::CouldNotFindEndOfBlock) }
+            None        => { return Err(TokenizerError::CouldNotFindEndOfBlock { span: content[start_index].span }) }
         }
     }
 
-    fn parse_conditional_loop(&mut self, parent: &mut Function, conditional_loop: Vec<String>) -> Token {
-        // Get necessary characters
-        let begin_condition_char = self.syntax_elements.assignment_symbols["begin loop condition"].clone();
-        let end_condition_char   = self.syntax_elements.assignment_symbols["end loop condition"].clone();
-        let begin_body_char      = self.syntax_elements.assignment_symbols["begin body"].clone();
+    /// Finds the index of the `]` closing the enclosure opened at `start_index`
+    ///
+    /// Enclosures opened inside of it (`::[`, `[`, ...) have to be closed first, so the
+    /// enclosure in `f::[g::[1], 2]` ends at the last `]`
+    fn find_end_of_enclosure(&self, content: &[Word], start_index: usize) -> Result<usize, TokenizerError> {
+        let end_enclosure_char = &self.syntax_elements.assignment_symbols["end enclosure"];
+
+        // Keep track of the amount of enclosures currently counted
+        let mut current_enclosure_counter: usize = 0;
+
+        let mut i = start_index;
+        while i < content.len() {
+            if content[i].ends_with('[') {
+                current_enclosure_counter += 1
+            }
+            else if content[i] == *end_enclosure_char {
+                current_enclosure_counter -= 1
+            }
+
+            // if the enclosure counter is 0, you reached the end
+            if current_enclosure_counter == 0 {
+                return Ok(i)
+            }
+
+            i += 1
+        }
+
+        return Err(TokenizerError::MissingSymbol { expected: end_enclosure_char.clone(), span: content[start_index..].span() })
+    }
+
+    /// Splits the contents of an enclosure by its condition seperators, ignoring the seperators
+    /// of enclosures inside of it
+    ///
+    /// `a, f::[b, c], d` becomes `[a]`, `[f::[b, c]]` and `[d]`
+    fn split_enclosure_contents<'a>(&self, content: &'a [Word]) -> Vec<&'a [Word]> {
+        let seperator_char     = &self.syntax_elements.assignment_symbols["condition seperator"];
+        let end_enclosure_char = &self.syntax_elements.assignment_symbols["end enclosure"];
+
+        let mut result: Vec<&[Word]> = Vec::new();
+
+        let mut current_enclosure_counter: usize = 0;
+        let mut current_start: usize = 0;
+        for (i, word) in content.iter().enumerate() {
+            if word.ends_with('[') {
+                current_enclosure_counter += 1
+            }
+            else if *word == *end_enclosure_char {
+                current_enclosure_counter = current_enclosure_counter.saturating_sub(1)
+            }
+            else if *word == *seperator_char && current_enclosure_counter == 0 {
+                result.push(&content[current_start..i]);
+                current_start = i + 1;
+            }
+        }
+        result.push(&content[current_start..]);
+
+        return result
+    }
+
+    /// Finds the index of the end assignment character ending the statement that starts at
+    /// `start_index`
+    fn find_end_of_assignment(&self, content: &Vec<Word>, start_index: usize) -> Result<usize, TokenizerError> {
+        let end_assignment_char = &self.syntax_elements.assignment_symbols["end assignment"];
+
+        match content.find_after_index(start_index, end_assignment_char) {
+            Some(index) => { return Ok(index) }
+            None        => { return Err(TokenizerError::MissingEndAssignment { span: content[start_index..].span() }) }
+        }
+    }
+
+    /// Finds where to carry on after a statement outside of a subroutine, which is the end of its
+    /// line or (if it opens a body, like a misspelled `subroutine`) the end of that body
+    fn find_end_of_stray_statement(&self, content: &Vec<Word>, start_index: usize) -> usize {
+        let line_end = self.find_end_of_assignment(content, start_index).unwrap_or(content.len());
+
+        match content[start_index..line_end].iter().position(|x| *x == self.syntax_elements.assignment_symbols["begin body"]) {
+            Some(body_start) => { return self.find_end_of_block(content, start_index + body_start).map_or(content.len(), |x| x + 1) }
+            None             => { return line_end }
+        }
+    }
+
+    /// Finds the first instance of the assignment symbol named `symbol_name` at or after
+    /// `start_index`
+    ///
+    /// Returns Err() pointing at the statement if the symbol is missing
+    fn find_symbol(&self, content: &Vec<Word>, start_index: usize, symbol_name: &str) -> Result<usize, TokenizerError> {
+        let symbol = &self.syntax_elements.assignment_symbols[symbol_name];
+
+        let found_index = if content.get(start_index).is_some_and(|x| x == symbol) {
+            Some(start_index)
+        } else {
+            content.find_after_index(start_index, symbol)
+        };
+
+        match found_index {
+            Some(index) => { return Ok(index) }
+            None        => { return Err(TokenizerError::MissingSymbol { expected: symbol.clone(), span: content[start_index..].span() }) }
+        }
+    }
+
+    /// Parses everything after `preceding_index` in `statement` into an Assignment
+    ///
+    /// Returns Err() pointing at the word at `preceding_index` if there is nothing after it
+    fn parse_assignment(&self, variable_history: &VariableHistory, statement: &[Word], preceding_index: usize) -> Result<Assignment, TokenizerError> {
+        let assignment_slice = statement[preceding_index+1..].to_vec();
+
+        if assignment_slice.is_empty() {
+            return Err(TokenizerError::MissingValue { span: statement[preceding_index].span })
+        }
+
+        return Assignment::from_string_vec(self, variable_history, assignment_slice)
+    }
+
+    /// Gets the name given right after the keyword starting `statement`
+    fn parse_name(&self, statement: &[Word]) -> Result<String, TokenizerError> {
+        match statement.get(1) {
+            Some(name) if !self.syntax_elements.get_all_symbols().contains(&name.text) => { return Ok(name.text.clone()) }
+            _ => { return Err(TokenizerError::MissingName { span: statement.span() }) }
+        }
+    }
+
+    fn parse_conditional_loop(&mut self, parent: &mut Function, conditional_loop: Vec<Word>) -> Result<Token, TokenizerError> {
+        // Parse the label, which is the only thing that can be written before the condition
+        let begin_condition_index = self.find_symbol(&conditional_loop, 0, "begin loop condition")?;
+        let label = match begin_condition_index {
+            1 => { None }
+            2 => { Some(self.parse_name(&conditional_loop)?) }
+            _ => { return Err(TokenizerError::MissingSymbol {
+                expected: self.syntax_elements.assignment_symbols["begin loop condition"].clone(),
+                span: conditional_loop[..begin_condition_index].span(),
+            })}
+        };
 
         // Parse the condition
-        let begin_condition_index = conditional_loop.find(&begin_condition_char).unwrap();
-        let end_condition_index   = conditional_loop.find(&end_condition_char).unwrap();
-        let condition_slice       = conditional_loop[begin_condition_index+1..=end_condition_index-1].to_vec();
+        let end_condition_index   = self.find_end_of_enclosure(&conditional_loop, begin_condition_index)?;
+        let condition_slice       = conditional_loop[begin_condition_index+1..end_condition_index].to_vec();
+        if condition_slice.is_empty() {
+            return Err(TokenizerError::MissingValue { span: conditional_loop[begin_condition_index].span })
+        }
         let condition             = Assignment::from_string_vec(self, &parent.variable_history, condition_slice);
 
-        // Parse the body
-        let begin_body_index = conditional_loop.find(&begin_body_char).unwrap();
-        let end_body_index   = self.find_end_of_block(&conditional_loop, begin_body_index).unwrap();
-        let body_slice       = conditional_loop[begin_body_index+1..=end_body_index-1].to_owned();
-        let functionality    = self.generate_token_tree(&mut Some(parent), &body_slice);
+        // Parse the body (even if the condition failed, so errors in it are still found)
+        let begin_body_index = self.find_symbol(&conditional_loop, end_condition_index, "begin body")?;
+        let end_body_index   = self.find_end_of_block(&conditional_loop, begin_body_index)?;
+        let body_slice       = conditional_loop[begin_body_index+1..end_body_index].to_owned();
+
+        self.enclosing_loops.push(label.clone());
+        parent.variable_history.begin_scope();
+        let functionality = self.generate_token_tree(&mut Some(parent), &body_slice);
+        parent.variable_history.end_scope();
+        self.enclosing_loops.pop();
 
         // Construct the token
         let conditional_loop_token = ConditionalLoop {
-            condition,
+            label,
+            condition: condition?,
             functionality,
+            span: conditional_loop.span(),
         };
 
         // Return it
-        return Token::ConditionalLoop(conditional_loop_token);
+        return Ok(Token::ConditionalLoop(conditional_loop_token));
+    }
+
+    /// Parses `loop i:=[int] from <start> to <end> step <step> : ... ;` (where `step` is
+    /// optional and defaults to 1)
+    ///
+    /// The loop variable is added to `parent`'s variable history while the body is parsed and
+    /// taken out of scope once it ends
+    fn parse_terminating_loop(&mut self, parent: &mut Function, terminating_loop: Vec<Word>) -> Result<Token, TokenizerError> {
+        // Parse the loop variable
+        let name                 = self.parse_name(&terminating_loop)?;
+        let begin_set_type_index = self.find_symbol(&terminating_loop, 1, "begin set type")?;
+        let end_set_type_index   = self.find_end_of_enclosure(&terminating_loop, begin_set_type_index)?;
+        let data_type            = self.parse_data_type(&terminating_loop[begin_set_type_index..=end_set_type_index])?;
+        let type_span            = terminating_loop[begin_set_type_index+1..end_set_type_index].span();
+
+        // Find the keywords of the header, which all come before the body
+        let begin_body_index = self.find_symbol(&terminating_loop, end_set_type_index, "begin body")?;
+        let header           = &terminating_loop[..begin_body_index];
+        let from_index       = self.find_keyword(header, end_set_type_index, "loop start")?;
+        let to_index         = self.find_keyword(header, from_index, "loop end")?;
+        let step_index       = header.to_vec().find_after_index(to_index, &self.syntax_elements.declaration_names["loop step"]);
+
+        // Parse the bounds, which can't see the loop variable
+        let from = self.parse_loop_bound(&parent.variable_history, header, from_index, to_index)?;
+        let to   = self.parse_loop_bound(&parent.variable_history, header, to_index, step_index.unwrap_or(begin_body_index))?;
+        let step = match step_index {
+            Some(step_index) => { self.parse_loop_bound(&parent.variable_history, header, step_index, begin_body_index)? }
+            None             => { Assignment::INTEGER(1, header.span()) }
+        };
+
+        // Get the body
+        let end_body_index = self.find_end_of_block(&terminating_loop, begin_body_index)?;
+        let body_slice     = terminating_loop[begin_body_index+1..end_body_index].to_owned();
+
+        // Declare the loop variable in a scope of its own, which the body is parsed in
+        parent.variable_history.begin_scope();
+        parent.variable_history.add_variable(Variable::new(&name, data_type.clone(), terminating_loop[1].span))?;
+        let location = parent.variable_history.find_variable(&name).ok_or(TokenizerError::IncorrectStackDataFormatting)?;
+
+        // Parse the body
+        self.enclosing_loops.push(Some(name.clone()));
+        let functionality = self.generate_token_tree(&mut Some(parent), &body_slice);
+        self.enclosing_loops.pop();
+        parent.variable_history.end_scope();
+
+        // Construct the token
+        let terminating_loop_token = TerminatingLoop {
+            name,
+            location,
+            data_type,
+            from,
+            to,
+            step,
+            functionality,
+            span: terminating_loop.span(),
+            type_span,
+        };
+
+        return Ok(Token::TerminatingLoop(Box::new(terminating_loop_token)))
     }
 
-    fn parse_conditional_statement(&mut self, parent: &mut Function, conditional_statement: Vec<String>) -> Result<Token, TokenizerError> {
+    fn parse_break(&self, break_statement: Vec<Word>) -> Result<Token, TokenizerError> {
+        let break_token = Break {
+            label: self.parse_loop_label(&break_statement)?,
+            span: break_statement.span(),
+        };
+
+        return Ok(Token::Break(break_token))
+    }
+
+    fn parse_continue(&self, continue_statement: Vec<Word>) -> Result<Token, TokenizerError> {
+        let continue_token = Continue {
+            label: self.parse_loop_label(&continue_statement)?,
+            span: continue_statement.span(),
+        };
+
+        return Ok(Token::Continue(continue_token))
+    }
+
+    /// Parses the label after a `break` or `continue`, making sure there's a loop around it
+    /// with that label (or any loop around it if there's no label)
+    fn parse_loop_label(&self, statement: &[Word]) -> Result<Option<String>, TokenizerError> {
+        if self.enclosing_loops.is_empty() {
+            return Err(TokenizerError::LoopControlOutsideLoop { keyword: statement[0].text.clone(), span: statement[0].span })
+        }
+
+        match statement {
+            [_, label] if !self.enclosing_loops.contains(&Some(label.text.clone())) => {
+                return Err(TokenizerError::UndeclaredLoopLabel { label: label.text.clone(), span: label.span })
+            }
+            [_, label]     => { return Ok(Some(label.text.clone())) }
+            [_, label, ..] => { return Err(TokenizerError::MissingEndAssignment { span: label.span }) }
+            _              => { return Ok(None) }
+        }
+    }
+
+    /// Finds the keyword `keyword_name` after `start_index` in `statement`
+    ///
+    /// Returns Err() pointing at the statement if the keyword is missing
+    fn find_keyword(&self, statement: &[Word], start_index: usize, keyword_name: &str) -> Result<usize, TokenizerError> {
+        let keyword = &self.syntax_elements.declaration_names[keyword_name];
+
+        match statement.to_vec().find_after_index(start_index, keyword) {
+            Some(index) => { return Ok(index) }
+            None        => { return Err(TokenizerError::MissingSymbol { expected: keyword.clone(), span: statement.span() }) }
+        }
+    }
+
+    /// Parses the equation between the loop keyword at `keyword_index` and `end_index`
+    fn parse_loop_bound(&self, variable_history: &VariableHistory, header: &[Word], keyword_index: usize, end_index: usize) -> Result<Assignment, TokenizerError> {
+        let bound_slice = header[keyword_index+1..end_index].to_vec();
+
+        if bound_slice.is_empty() {
+            return Err(TokenizerError::MissingValue { span: header[keyword_index].span })
+        }
+
+        return Assignment::from_string_vec(self, variable_history, bound_slice)
+    }
+
+    fn parse_conditional_statement(&mut self, parent: &mut Function, conditional_statement: Vec<Word>) -> Result<Token, TokenizerError> {
         // Get necessary characters
-        let begin_comparison_conditions_char = self.syntax_elements.assignment_symbols["begin comparison conditions"].clone();
-        let end_comparison_conditions_char   = self.syntax_elements.assignment_symbols["end comparison conditions"].clone();
-        let begin_enclosure_char             = self.syntax_elements.assignment_symbols["begin enclosure"].clone();
-        let end_enclosure_char               = self.syntax_elements.assignment_symbols["end enclosure"].clone();
-        let begin_block_char                 = self.syntax_elements.assignment_symbols["begin body"].clone();
-        let else_comparison_statement_char   = self.syntax_elements.declaration_names["else conditional statement"].clone();
+        let begin_enclosure_char           = self.syntax_elements.assignment_symbols["begin enclosure"].clone();
+        let else_comparison_statement_char = self.syntax_elements.declaration_names["else conditional statement"].clone();
 
         // Get active variables slice
-        let begin_comparison_conditions_index = conditional_statement.find(&begin_comparison_conditions_char).unwrap();
-        let end_comparison_conditions_index   = conditional_statement.find(&end_comparison_conditions_char  ).unwrap();
-        let comparison_conditions_slice_raw: Vec<String> = conditional_statement[begin_comparison_conditions_index+1..=end_comparison_conditions_index-1].to_vec();
-        let comparison_conditions_slice: Vec<&[String]> = comparison_conditions_slice_raw.split(|x| x==",").collect();
+        let begin_comparison_conditions_index = self.find_symbol(&conditional_statement, 0, "begin comparison conditions")?;
+        let end_comparison_conditions_index   = self.find_end_of_enclosure(&conditional_statement, begin_comparison_conditions_index)?;
+        let comparison_conditions_slice_raw: Vec<Word> = conditional_statement[begin_comparison_conditions_index+1..end_comparison_conditions_index].to_vec();
+        let comparison_conditions_slice: Vec<&[Word]> = self.split_enclosure_contents(&comparison_conditions_slice_raw);
 
         // Parse it by iterating over each variable passed
         let mut active_variables: Vec<usize> = Vec::new();
-        for variable in comparison_conditions_slice.iter() {
-            let variable_location = parent.variable_history.find_variable(&variable[0]).unwrap();
+        if !comparison_conditions_slice_raw.is_empty() { for variable in comparison_conditions_slice.iter() {
+            let Some(variable_name) = variable.first() else {
+                return Err(TokenizerError::MissingName { span: comparison_conditions_slice_raw.span() })
+            };
+            let variable_location = parent.variable_history.find_variable(variable_name)
+                .ok_or(parent.variable_history.not_found(&variable_name.text, variable_name.span))?;
             active_variables.push(variable_location);
-        }
+        }}
 
         // Get each condition field
         let mut condition_fields_slices: Vec<(Option<Assignment>, Vec<Token>)> = Vec::new();
-        let mut i = conditional_statement.find(&begin_enclosure_char).unwrap();
+        let mut i = self.find_symbol(&conditional_statement, end_comparison_conditions_index, "begin enclosure")?;
         while i < conditional_statement.len() {
             // get index of necessary chars
-            let end_enclosure_index = conditional_statement.find_after_index(i, &end_enclosure_char).unwrap();
-            let enclosure_slice     = conditional_statement[i+1..=end_enclosure_index-1].to_owned();
+            let end_enclosure_index = self.find_end_of_enclosure(&conditional_statement, i)?;
+            let enclosure_slice     = conditional_statement[i+1..end_enclosure_index].to_owned();
 
             // get the condition of current field
-            let field_condition: Option<Assignment>;
-            if enclosure_slice == [else_comparison_statement_char.clone()] {
-                field_condition = None;
+            // a condition that fails to parse is recorded (rather than returned) so the rest of
+            // the branches can still be tokenized
+            let mut field_condition: Result<Option<Assignment>, TokenizerError>;
+            if enclosure_slice.is_empty() {
+                field_condition = Err(TokenizerError::MissingValue { span: conditional_statement[i].span });
+            } else if enclosure_slice == [else_comparison_statement_char.clone()] {
+                field_condition = Ok(None);
             } else {
-                field_condition = Some(Assignment::from_string_vec(self, &parent.variable_history, enclosure_slice));
+                field_condition = Assignment::from_string_vec(self, &parent.variable_history, enclosure_slice).map(Some);
             }
 
             // get the block index and parse it
-            let block_start_index  = conditional_statement.find_after_index(i, &begin_block_char).unwrap();
-            let block_end_index    = self.find_end_of_block(&conditional_statement, block_start_index).unwrap();
-            let inline_block_slice = conditional_statement[block_start_index+1..=block_end_index-1].to_owned();
+            let block_start_index  = self.find_symbol(&conditional_statement, end_enclosure_index, "begin body")?;
+            let block_end_index    = self.find_end_of_block(&conditional_statement, block_start_index)?;
+            let inline_block_slice = conditional_statement[block_start_index+1..block_end_index].to_owned();
+            parent.variable_history.begin_scope();
             let inline_block = self.generate_token_tree(&mut Some(parent), &inline_block_slice);
+            parent.variable_history.end_scope();
 
             // make sure each variable was passed
-            if let Some(assignment) = &field_condition {
+            if let Ok(Some(assignment)) = &field_condition {
                 let vars_used_in_assignment = assignment.get_all_vars_used();
-                for var in vars_used_in_assignment.iter() {
-                    if let None = active_variables.find(var) { return Err(TokenizerError::VarNotUsedInComparison) }
+                let unpassed_var = vars_used_in_assignment.iter().find(|x| active_variables.find(*x).is_none());
+
+                if let Some(var) = unpassed_var {
+                    let name = parent.variable_history.data[*var].clone().map(|x| x.name).unwrap_or_default();
+                    field_condition = Err(TokenizerError::VarNotUsedInComparison { name, span: assignment.span() });
                 }
             }
 
             // push this
-            condition_fields_slices.push((field_condition, inline_block));
+            if let Some(field_condition) = self.recover(field_condition) {
+                condition_fields_slices.push((field_condition, inline_block));
+            }
 
             // if there is another enclosure, jump i to there. If not, break the loop
-            if let Some(found_index) = conditional_statement.find_after_index(end_enclosure_index, &begin_enclosure_char) {
+            if let Some(found_index) = conditional_statement.find_after_index(block_end_index, &begin_enclosure_char) {
                 i = found_index
             } else {
                 break;
@@ -350,113 +768,117 @@ pub struct Tokenizer {
         let conditional_statement_token = ConditionalStatement {
             active_variables,
             condition_fields: condition_fields_slices,
+            span: conditional_statement.span(),
         };
 
         return Ok(Token::ConditionalStatement(conditional_statement_token))
     }
 
-    fn parse_reassignment(&self, variable_history: &VariableHistory, reassignment: Vec<String>) -> Token {
-        // Get the necessary characters
-        let equals_char = self.syntax_elements.assignment_symbols.get("equals").unwrap();
-
+    fn parse_reassignment(&self, variable_history: &VariableHistory, reassignment: Vec<Word>) -> Result<Token, TokenizerError> {
         // Parse the declaration
-        let equal_sign_index = reassignment.find(equals_char).unwrap();
-
-        // Get the assignment part (everything after equals and before `\n`)
-        let string_assignment = reassignment[equal_sign_index+1..reassignment.len()].to_vec();
-
-        // Retrieve the name of te variable and what it's newly assigned to
-        let name = reassignment[1].clone();
-        let new_assignment: Assignment = Assignment::from_string_vec(&self, variable_history, string_assignment);
+        let name = self.parse_name(&reassignment)?;
+        let equal_sign_index = self.find_symbol(&reassignment, 1, "equals")?;
 
         // Ensure the variable is in variable_history
-        variable_history.find_variable(&name).expect("Variable does not exist");
+        let Some(variable_location) = variable_history.find_variable(&name) else {
+            return Err(variable_history.not_found(&name, reassignment[1].span))
+        };
+        let data_type = variable_history.data[variable_location].clone().ok_or(TokenizerError::IncorrectStackDataFormatting)?.data_type;
+
+        // Retrieve what the variable is newly assigned to (everything after equals and before `\n`)
+        let new_assignment: Assignment = self.parse_assignment(variable_history, &reassignment, equal_sign_index)?
+            .promote_to(&data_type, variable_history);
 
         // Build the declaration token
         let reassignment_token = Reassignment {
             name,
+            location: variable_location,
             new_assignment,
+            span: reassignment.span(),
         };
 
-        return Token::Reassignment(reassignment_token)
+        return Ok(Token::Reassignment(reassignment_token))
     }
 
-    fn parse_variable(&self, variable_history: &mut VariableHistory, declaration: Vec<String>) -> Token {
-        // Get the necessary characters
-        let equals_char         = self.syntax_elements.assignment_symbols.get("equals").unwrap();
-        let begin_set_type_char = self.syntax_elements.assignment_symbols.get("begin set type").unwrap();
-        let end_set_type_char   = self.syntax_elements.assignment_symbols.get("end set type").unwrap();
-
+    fn parse_variable(&self, variable_history: &mut VariableHistory, declaration: Vec<Word>) -> Result<Token, TokenizerError> {
         // Parse the declaration
-        let equal_sign_index     = declaration.find(equals_char).unwrap();
-        let begin_set_type_index = declaration.find(begin_set_type_char).unwrap();
-        let end_set_type_index   = declaration.find(end_set_type_char).unwrap();
-
-        // Get the assignment part (everything after equals and before `;`)
-        let string_assignment = declaration[equal_sign_index+1..declaration.len()].to_vec();
-        let data_type_slice = declaration[begin_set_type_index+1..=end_set_type_index-1].to_vec();
-
-        // Retrieve the name of te variable, its data_type, and what it's assigned to
-        let name = declaration[1].clone();
-        let data_type = DataType::check_token_type(&data_type_slice[0]).unwrap();
-        let assignment: Assignment = Assignment::from_string_vec(&self, variable_history, string_assignment);
+        let name                 = self.parse_name(&declaration)?;
+        let begin_set_type_index = self.find_symbol(&declaration, 1, "begin set type")?;
+        let end_set_type_index   = self.find_end_of_enclosure(&declaration, begin_set_type_index)?;
+        let equal_sign_index     = self.find_symbol(&declaration, end_set_type_index, "equals")?;
+
+        // Retrieve its data_type and what it's assigned to (everything after equals and before `\n`)
+        let data_type = self.parse_data_type(&declaration[begin_set_type_index..=end_set_type_index])?;
+        let assignment: Assignment = self.parse_assignment(variable_history, &declaration, equal_sign_index)?
+            .promote_to(&data_type, variable_history);
+
         // Add it to representation variable_history
-        let variable_representation = Variable {
-            name: name.clone(),
-            data_type: data_type.clone(),
-        };
-        variable_history.add_variable(variable_representation)
-            .expect("variable_history does not conclude with None");
+        let variable_representation = Variable::new(&name, data_type.clone(), declaration[1].span);
+        variable_history.add_variable(variable_representation)?;
 
         // Build the declaration token
         let declaration = Declaration {
             name: name.to_string(),
-            location: variable_history.find_variable(&name).unwrap(),
+            location: variable_history.find_variable(&name).ok_or(TokenizerError::IncorrectStackDataFormatting)?,
             data_type,
             value: assignment,
+            span: declaration.span(),
         };
 
-        return Token::Declaration(declaration)
+        return Ok(Token::Declaration(declaration))
     }
 
-    fn parse_function(&mut self, declaration: Vec<String>) -> Token {
-        // Get necessary characters
-        let block_start_char = self.syntax_elements.assignment_symbols.get("begin body")
-            .unwrap();
-        let return_this_char = self.syntax_elements.assignment_symbols.get("return this")
-            .unwrap();
-        let begin_conditions_char = self.syntax_elements.assignment_symbols.get("begin conditions")
-            .unwrap();
-        let end_conditions_char = self.syntax_elements.assignment_symbols.get("end conditions")
-            .unwrap();
+    /// Parses the type held between a `:=[` and `]`
+    fn parse_data_type(&self, type_enclosure: &[Word]) -> Result<DataType, TokenizerError> {
+        match type_enclosure {
+            [_, data_type, _] => { return DataType::check_token_type(data_type)
+                .ok_or(TokenizerError::UnknownType { name: data_type.text.clone(), span: data_type.span }) }
+            [_, _]            => { return Err(TokenizerError::MissingType { span: type_enclosure.span() }) }
+            _                 => { return Err(TokenizerError::UnknownType {
+                name: type_enclosure[1..type_enclosure.len()-1].iter().map(|x| x.text.clone()).collect::<Vec<String>>().join(" "),
+                span: type_enclosure[1..type_enclosure.len()-1].span(),
+            })}
+        }
+    }
 
+    fn parse_function(&mut self, declaration: Vec<Word>) -> Result<Token, TokenizerError> {
         // Get the indexes of the necessary characters
-        let block_start_index = declaration.find(block_start_char).unwrap();
-        let return_this_index = declaration.find(return_this_char).unwrap();
-        let begin_conditions_index = declaration.find(begin_conditions_char).unwrap();
-        let end_conditions_index = declaration.find(end_conditions_char).unwrap();
+        let name                   = self.parse_name(&declaration)?;
+        let block_start_index      = self.find_symbol(&declaration, 0, "begin body")?;
+        let begin_conditions_index = self.find_symbol(&declaration, 1, "begin conditions")?;
+        let end_conditions_index   = self.find_end_of_enclosure(&declaration, begin_conditions_index)?;
+        let return_this_index      = match self.find_symbol(&declaration, end_conditions_index, "return this") {
+            Ok(index) if index < block_start_index => { index }
+            _ => { return Err(TokenizerError::MissingReturnArrow { name, span: declaration[..=block_start_index].span() }) }
+        };
 
         // Get the function block and given argument slices
-        let inline_block_slice = declaration[block_start_index+1..].to_vec();
-        let argument_slice_raw = declaration[begin_conditions_index+1..=end_conditions_index-1].to_vec();
-        let argument_slice: Vec<&[String]> = argument_slice_raw.split(|x| x==",").collect();
+        let inline_block_slice = declaration[block_start_index+1..declaration.len()-1].to_vec();
+        let argument_slice_raw = declaration[begin_conditions_index+1..end_conditions_index].to_vec();
+        let argument_slice: Vec<&[Word]> = self.split_enclosure_contents(&argument_slice_raw);
 
         // Parse the arguments by iterating over each of them
+        let arguments_span = declaration[begin_conditions_index..=end_conditions_index].span();
         let mut arguments: Vec<Variable> = Vec::new();
-        if argument_slice[0].len() > 0 { for argument in argument_slice {
-            arguments.push( Variable::from_function_arg(argument.to_vec()) );
+        if !argument_slice_raw.is_empty() { for argument in argument_slice {
+            arguments.push( Variable::from_function_arg(argument.to_vec(), arguments_span)? );
         }}
 
         // Create the function's variable history and add the arguments to it
         let mut variable_history = VariableHistory::init(MEMORY_STEP);
         for argument in arguments.iter() {
-            variable_history.add_variable(argument.to_owned()).unwrap();
+            variable_history.add_variable(argument.to_owned()).map_err(|error| match error {
+                TokenizerError::RedeclaredVariable { name, span, previous_span } => { TokenizerError::DuplicateArgument { name, span, previous_span } }
+                error => { error }
+            })?;
         }
 
         // Parse the function with the given infomation
-        let name = declaration[1].to_string();
-        let return_type_text = declaration[return_this_index+1].to_owned();
-        let return_type = DataType::check_token_type(&return_type_text).unwrap();
+        let return_type = match &declaration[return_this_index+1..block_start_index] {
+            [return_type_text] => { DataType::check_token_type(return_type_text)
+                .ok_or(TokenizerError::UnknownType { name: return_type_text.text.clone(), span: return_type_text.span })? }
+            _ => { return Err(TokenizerError::MissingType { span: declaration[return_this_index].span }) }
+        };
 
         // Construct the function
         let mut function = Function {
@@ -465,6 +887,7 @@ pub struct Tokenizer {
             variable_history,
             arguments,
             functionaliy: Vec::new(),
+            span: declaration.span(),
         };
 
         // Define the function's functionality
@@ -472,20 +895,18 @@ pub struct Tokenizer {
         function.functionaliy = inline_block;
 
         // Return it
-        return Token::Function(function)
+        return Ok(Token::Function(function))
     }
 
-    fn parse_return(&self, parent: &Function, return_statement: Vec<String>) -> Token {
-        let assignment_slice = return_statement[1..].to_vec();
-
-        let assignment: Assignment = {
-            Assignment::from_string_vec(&self, &parent.variable_history, assignment_slice)
-        };
+    fn parse_return(&self, parent: &Function, return_statement: Vec<Word>) -> Result<Token, TokenizerError> {
+        let assignment: Assignment = self.parse_assignment(&parent.variable_history, &return_statement, 0)?
+            .promote_to(&parent.return_type, &parent.variable_history);
         
         let return_token = Return {
             assignment,
+            span: return_statement.span(),
         };
 
-        return Token::Return(return_token)
+        return Ok(Token::Return(return_token))
     }
 }
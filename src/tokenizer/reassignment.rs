@@ -1,8 +1,11 @@
 use super::enumerators::Assignment;
+use crate::optimizer::span::Span;
 
 
 #[derive(Debug, Clone)]
 pub struct Reassignment {
     pub name: String,
+    pub location: usize,
     pub new_assignment: Assignment,
+    pub span: Span,
 } 
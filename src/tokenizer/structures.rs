@@ -1,6 +1,7 @@
 use super::declaration::DataType;
 use super::error::TokenizerError;
 use super::function::Function;
+use crate::optimizer::span::{Span, Word, WordSliceExtra};
 
 
 #[derive(Debug, Clone)]
@@ -29,8 +30,15 @@ pub struct FunctionHistory {
     }
 
     /// Add a `Function` to this `FunctionHistory`
-    pub fn add_function(&mut self, function: Function) {
+    ///
+    /// Returns Err() if a function with the same name was already added
+    pub fn add_function(&mut self, function: Function) -> Result<(), TokenizerError> {
+        if let Some(previous) = self.find_by_name(&function.name) {
+            return Err(TokenizerError::DuplicateFunction { name: function.name, span: function.span, previous_span: previous.span })
+        }
+
         self.data.push(function);
+        return Ok(())
     }
 }
 
@@ -39,25 +47,55 @@ pub struct FunctionHistory {
 pub struct Variable {
     pub name: String,
     pub data_type: DataType,
+    /// The stack slot this variable is held in, which it may share with variables from scopes
+    /// that ended before it was declared
+    pub slot: usize,
+    /// Whether this variable can still be found by name
+    ///
+    /// Variables that go out of scope keep their place in VariableHistory (and their type), as
+    /// statements that were already tokenized still refer to them by location
+    pub in_scope: bool,
+    /// The span of the name this variable was declared with
+    pub span: Span,
 } impl Variable {
-    pub fn new(name: &str, data_type: DataType) -> Self { Self {
+    pub fn new(name: &str, data_type: DataType, span: Span) -> Self { Self {
         name: name.to_string(),
         data_type,
+        slot: 0,
+        in_scope: true,
+        span,
     }}
 
-    pub fn from_function_arg(from: Vec<String>) -> Self { Self {
-        name: from[1].clone(),
-        data_type: DataType::check_token_type(&from[0]).unwrap(),
-    }}
+    /// Parses an argument given to a function (`int first`) into a Variable
+    ///
+    /// `arguments_span` is the span of the whole argument list, which is pointed at when the
+    /// argument is empty (as in `::[int a, ]`)
+    pub fn from_function_arg(from: Vec<Word>, arguments_span: Span) -> Result<Self, TokenizerError> {
+        let [data_type_text, name] = from.as_slice() else {
+            let span = if from.is_empty() { arguments_span } else { from.span() };
+            return Err(TokenizerError::MissingName { span })
+        };
+
+        let data_type = DataType::check_token_type(data_type_text)
+            .ok_or(TokenizerError::UnknownType { name: data_type_text.text.clone(), span: data_type_text.span })?;
+
+        return Ok(Self::new(&name.text, data_type, name.span))
+    }
 }
 
 
 /// Structure representing the stack memory of the program (with some constraints)
 ///   - All variables are of equal size
+///   - Every variable ever declared is kept (its index is its `location`), but only the
+///     variables of the scopes still open are in scope
+///   - Scopes end in the opposite order they begin, so the variables in scope always fill the
+///     first slots and a scope's slots are reused once it ends
 #[derive(Debug, Clone)]
 pub struct VariableHistory {
     pub data: Vec<Option<Variable>>,
     pub step: usize,
+    /// How many variables were declared before each open scope began, innermost last
+    scopes: Vec<usize>,
 
 } impl VariableHistory {
     /// Initiates stack memory representation (`Memory`) for the program
@@ -72,9 +110,20 @@ pub struct VariableHistory {
     pub fn init(step: usize) -> Self { return Self {
         data: vec![None],
         step,
+        scopes: Vec::new(),
     }}
 
-    pub fn add_variable(&mut self, variable: Variable) -> Result<(), TokenizerError> {
+    /// Adds `variable` to the innermost scope, in the first slot no variable in scope holds
+    ///
+    /// Returns Err() if a variable with the same name was already declared in the innermost
+    /// scope (variables in outer scopes can be shadowed)
+    pub fn add_variable(&mut self, mut variable: Variable) -> Result<(), TokenizerError> {
+        // Make sure the name isn't taken in this scope
+        let scope_start = self.scopes.last().copied().unwrap_or(0);
+        if let Some(previous) = self.data[scope_start..].iter().flatten().find(|x| x.in_scope && x.name == variable.name) {
+            return Err(TokenizerError::RedeclaredVariable { name: variable.name, span: variable.span, previous_span: previous.span })
+        }
+
         // Initiate this variable to manage error handling
         let mut new_variable_location: Option<usize> = None;
 
@@ -85,6 +134,7 @@ pub struct VariableHistory {
 
         // Replace that None value with the new variable...
         if let Some(unwrapped_new_variable_location) = new_variable_location {
+            variable.slot = self.data.iter().flatten().filter(|x| x.in_scope).count();
             self.data[unwrapped_new_variable_location] = Some(variable);
 
             if unwrapped_new_variable_location == self.data.len() - 1 {
@@ -98,9 +148,10 @@ pub struct VariableHistory {
         }
     }
     
-    /// Finds a variable (by name) and returns the slot it's held in
+    /// Finds a variable (by name) and returns the location it's held in
     ///
-    /// Returns None if the variable is not found
+    /// Returns None if the variable is not found (or is out of scope). If a variable in an
+    /// inner scope shadows one in an outer scope, the inner one is found
     ///
     /// # Examples
     ///
@@ -123,9 +174,49 @@ pub struct VariableHistory {
         let mut result: Option<usize> = None;
 
         for (i, variable) in self.data.iter().enumerate() { if let Some(variable) = variable {
-            if variable_name == variable.name { result = Some(i) }
+            if variable.in_scope && variable_name == variable.name { result = Some(i) }
         }} 
 
         return result
     }
+
+    /// The error for using `variable_name` where it can't be found, which tells apart a variable
+    /// that was never declared from one whose scope already ended
+    pub fn not_found(&self, variable_name: &str, span: Span) -> TokenizerError {
+        if self.data.iter().flatten().any(|x| x.name == variable_name) {
+            return TokenizerError::VariableOutOfScope { name: variable_name.to_string(), span }
+        } else {
+            return TokenizerError::UndeclaredVariable { name: variable_name.to_string(), span }
+        }
+    }
+
+    /// Begins a new scope inside the current one
+    pub fn begin_scope(&mut self) {
+        self.scopes.push(self.data.len() - 1);
+    }
+
+    /// Ends the innermost scope, taking every variable declared in it out of scope and freeing
+    /// their slots
+    pub fn end_scope(&mut self) {
+        let Some(scope_start) = self.scopes.pop() else { return };
+
+        for variable in self.data[scope_start..].iter_mut().flatten() {
+            variable.in_scope = false;
+        }
+    }
+
+    /// The number of bytes below `rbp` the variable at `location` is held at
+    ///
+    /// The first slot below `rbp` is where the subroutine saves the register it calls with, so the
+    /// variables start after it
+    pub fn stack_offset(&self, location: usize) -> usize {
+        let slot = self.data[location].as_ref().map_or(location, |x| x.slot);
+
+        return (slot + 2) * self.step
+    }
+
+    /// The number of slots the variables need, which is the most ever in scope at once
+    pub fn slot_count(&self) -> usize {
+        return self.data.iter().flatten().map(|x| x.slot + 1).max().unwrap_or(0)
+    }
 }
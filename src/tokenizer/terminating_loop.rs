@@ -1,2 +1,22 @@
+use super::enumerators::Assignment;
+use super::declaration::DataType;
+use super::Token;
+use crate::optimizer::span::Span;
+
+
+/// A loop that counts its variable from `from` up to (but not including) `to`, adding `step`
+/// after every pass
+///
+/// The loop variable is only in scope inside `functionality`
 #[derive(Debug, Clone)]
-pub struct TerminatingLoop {}
+pub struct TerminatingLoop {
+    pub name: String,
+    pub location: usize,
+    pub data_type: DataType,
+    pub from: Assignment,
+    pub to: Assignment,
+    pub step: Assignment,
+    pub functionality: Vec<Token>,
+    pub span: Span,
+    pub type_span: Span,
+}
@@ -127,15 +127,15 @@ pub trait VecExtra<T: Eq + Clone> {
     /// Returns the index of the first instance of `pattern` in this vector
     ///
     /// Returns None if `pattern` is not found in this vector
-    fn find(&self, pattern: &T) -> Option<usize>;
+    fn find<P: ?Sized>(&self, pattern: &P) -> Option<usize> where T: PartialEq<P>;
 
     /// Returns the index of the first instance of any elements in `patterns` in this vector
     ///
     /// Returns None if none of the elements in `patterns` are found
-    fn find_from_vec(&self, patterns: &[T]) -> Option<usize>;
+    fn find_from_vec<P>(&self, patterns: &[P]) -> Option<usize> where T: PartialEq<P>;
 
     /// Returns the index of the first matched pattern after a given index
-    fn find_after_index(&self, index: usize, pattern: &T) -> Option<usize>;
+    fn find_after_index<P: ?Sized>(&self, index: usize, pattern: &P) -> Option<usize> where T: PartialEq<P>;
 
     /// Copys all elements from `other` and appends them into this vector
     ///
@@ -143,25 +143,25 @@ pub trait VecExtra<T: Eq + Clone> {
     fn append_immut(&mut self, appended_slice: &[T]);
 }
 impl<T: Eq + Clone> VecExtra<T> for Vec<T> {
-    fn find(&self, pattern: &T) -> Option<usize> {
+    fn find<P: ?Sized>(&self, pattern: &P) -> Option<usize> where T: PartialEq<P> {
         let mut result: Option<usize> = None;
 
         // Find the first instance of the given pattern
         for (i, element) in self.iter().enumerate() {
-            if element == pattern { result = Some(i); break; }
+            if *element == *pattern { result = Some(i); break; }
         }
 
         return result
     }
 
-    fn find_from_vec(&self, patterns: &[T]) -> Option<usize> {
+    fn find_from_vec<P>(&self, patterns: &[P]) -> Option<usize> where T: PartialEq<P> {
         let mut result: Option<usize> = None;
 
         for (element_index, element) in self.iter().enumerate() {
             let mut pattern_found = false;
 
             for pattern in patterns.iter() {
-                if element == pattern { result = Some(element_index); pattern_found = true; break; }
+                if *element == *pattern { result = Some(element_index); pattern_found = true; break; }
             }
 
             if pattern_found { break }
@@ -170,11 +170,11 @@ impl<T: Eq + Clone> VecExtra<T> for Vec<T> {
         return result
     }
 
-    fn find_after_index(&self, index: usize, pattern: &T) -> Option<usize> {
+    fn find_after_index<P: ?Sized>(&self, index: usize, pattern: &P) -> Option<usize> where T: PartialEq<P> {
         let mut result: Option<usize> = None;
 
         for (i, element) in self.iter().enumerate() {
-            if element == pattern && i > index {
+            if *element == *pattern && i > index {
                 result = Some(i); break; 
             }
         }
@@ -0,0 +1,97 @@
+use thiserror::Error;
+
+use crate::data::SyntaxElements;
+use crate::diagnostics::{Diagnostic, ToDiagnostic};
+use crate::optimizer::span::Span;
+
+
+#[derive(Error, Debug)]
+pub enum TypeError {
+    #[error("mismatched types: expected `{expected}`, found `{found}`")]
+    MismatchedTypes { expected: String, found: String, span: Span },
+    #[error("the `{operator}` operator cannot be used on `{first}` and `{second}`")]
+    InvalidOperands { operator: String, first: String, second: String, span: Span },
+    #[error("the `{operator}` operator cannot be used on a `{found}`")]
+    InvalidOperand { operator: String, found: String, span: Span },
+    #[error("cannot convert a `{from}` to a `{to}`")]
+    InvalidConversion { from: String, to: String, span: Span },
+    #[error("wrong number of arguments passed to `{name}`")]
+    WrongArgumentCount { name: String, expected: usize, found: usize, span: Span, definition_span: Span },
+    #[error("the subroutine `{name}` does not `expose` a value on every path")]
+    MissingReturn { name: String, span: Span },
+} impl TypeError {
+    /// The error code shown alongside this error when it's rendered
+    pub fn code(&self) -> &'static str { match self {
+        Self::MismatchedTypes { .. }    => { "E0300" }
+        Self::InvalidOperands { .. }    => { "E0301" }
+        Self::InvalidOperand { .. }     => { "E0302" }
+        Self::InvalidConversion { .. }  => { "E0303" }
+        Self::WrongArgumentCount { .. } => { "E0304" }
+        Self::MissingReturn { .. }      => { "E0305" }
+    }}
+
+    /// The span of the source text that caused this error
+    pub fn span(&self) -> Span { match self {
+        Self::MismatchedTypes { span, .. }    => { *span }
+        Self::InvalidOperands { span, .. }    => { *span }
+        Self::InvalidOperand { span, .. }     => { *span }
+        Self::InvalidConversion { span, .. }  => { *span }
+        Self::WrongArgumentCount { span, .. } => { *span }
+        Self::MissingReturn { span, .. }      => { *span }
+    }}
+
+    /// What to write under the span of this error
+    pub fn label(&self) -> String { match self {
+        Self::MismatchedTypes { expected, .. }           => { format!("expected `{expected}`") }
+        Self::InvalidOperands { first, second, .. }      => { format!("`{first}` and `{second}`") }
+        Self::InvalidOperand { found, .. }               => { format!("this is a `{found}`") }
+        Self::InvalidConversion { .. }                   => { "invalid conversion".into() }
+        Self::WrongArgumentCount { expected, found, .. } => { format!("expected {expected} argument{}, found {found}", if *expected == 1 { "" } else { "s" }) }
+        Self::MissingReturn { .. }                       => { "this subroutine can reach its end without an `expose`".into() }
+    }}
+
+    /// A second span of source text to point at for context, and what to write under it
+    pub fn secondary_label(&self) -> Option<(Span, String)> { match self {
+        Self::WrongArgumentCount { definition_span, .. } => { Some((*definition_span, "defined here".into())) }
+                                                       _ => { None }
+    }}
+
+    /// A suggestion on how to fix this error, if there is one
+    pub fn help(&self) -> Option<&'static str> { match self {
+        Self::MismatchedTypes { expected, found, .. } if is_number(expected) && is_number(found) => {
+            Some("`int` and `flt` values can be converted between each other with `as`, e.g. `x as int`")
+        }
+        Self::InvalidConversion { .. } => {
+            Some("only `int` and `flt` can be converted between each other, and `tof` can be converted to `int`")
+        }
+        Self::MissingReturn { .. } => {
+            Some("add an `expose` at the end of the subroutine, or an `otherwise` branch to the `cmpr` that ends it")
+        }
+        _ => { None }
+    }}
+}
+
+impl ToDiagnostic for TypeError {
+    fn to_diagnostic(&self) -> Diagnostic {
+        let mut diagnostic = Diagnostic::error(&self.to_string())
+            .with_code(self.code())
+            .with_label(self.span(), &self.label());
+
+        if let Some((span, label)) = self.secondary_label() {
+            diagnostic = diagnostic.with_secondary_label(span, &label);
+        }
+
+        if let Some(help) = self.help() {
+            diagnostic = diagnostic.with_help(help);
+        }
+
+        return diagnostic
+    }
+}
+
+
+/// Whether `type_name` is the name of a type that can be used in arithmetic
+fn is_number(type_name: &str) -> bool {
+    let syntax_elements = SyntaxElements::init();
+    return type_name == syntax_elements.type_names["integer"] || type_name == syntax_elements.type_names["float"]
+}
@@ -0,0 +1,262 @@
+use crate::tokenizer::Token;
+use crate::tokenizer::function::Function;
+use crate::tokenizer::declaration::DataType;
+use crate::tokenizer::structures::{FunctionHistory, VariableHistory};
+use crate::tokenizer::enumerators::{Assignment, ComparisonOperator, MathOperator, UnaryOperator};
+use crate::optimizer::span::Span;
+
+
+#[allow(dead_code)]
+pub mod error;
+    use error::TypeError;
+
+
+/// Checks that every value in a token tree is used where its type is expected
+///
+/// This runs after the Tokenizer and before the Assembler, so the Assembler can assume every
+/// Assignment it is given makes sense
+pub struct TypeChecker {
+    errors: Vec<TypeError>,
+    function_history: FunctionHistory,
+
+} impl TypeChecker {
+    pub fn init() -> Self { Self {
+        errors: Vec::new(),
+        function_history: FunctionHistory::init(),
+    }}
+
+    /// Checks every function in `token_tree`
+    ///
+    /// Returns every type error found, in the order they appear in the source
+    pub fn check_token_tree(&mut self, token_tree: &[Token]) -> Result<(), Vec<TypeError>> {
+        // Collect every function first so calls can be checked against their signature
+        // (the Tokenizer already left out any function declared twice)
+        for token in token_tree.iter() { if let Token::Function(function) = token {
+            let _ = self.function_history.add_function(function.clone());
+        }}
+
+        for token in token_tree.iter() { if let Token::Function(function) = token {
+            self.check_block(function, &function.functionaliy);
+
+            if !always_returns(&function.functionaliy) {
+                self.errors.push(TypeError::MissingReturn { name: function.name.clone(), span: function.span });
+            }
+        }}
+
+        if self.errors.is_empty() {
+            return Ok(())
+        } else {
+            let mut errors = std::mem::take(&mut self.errors);
+            errors.sort_by_key(|x| x.span().start);
+
+            return Err(errors)
+        }
+    }
+
+    /// Checks every statement in `block`, where `parent` is the function it's in
+    fn check_block(&mut self, parent: &Function, block: &[Token]) {
+        let variable_history = &parent.variable_history;
+
+        for token in block.iter() { match token {
+            Token::Declaration(declaration) => {
+                self.expect_type(variable_history, &declaration.value, &declaration.data_type);
+            }
+            Token::Reassignment(reassignment) => {
+                let data_type = variable_history.data[reassignment.location].clone().unwrap().data_type;
+
+                self.expect_type(variable_history, &reassignment.new_assignment, &data_type);
+            }
+            Token::Return(return_statement) => {
+                self.expect_type(variable_history, &return_statement.assignment, &parent.return_type);
+            }
+            Token::ConditionalLoop(conditional_loop) => {
+                self.expect_type(variable_history, &conditional_loop.condition, &DataType::BOOL);
+                self.check_block(parent, &conditional_loop.functionality);
+            }
+            Token::ConditionalStatement(conditional_statement) => {
+                for (condition, functionality) in conditional_statement.condition_fields.iter() {
+                    if let Some(condition) = condition {
+                        self.expect_type(variable_history, condition, &DataType::BOOL);
+                    }
+
+                    self.check_block(parent, functionality);
+                }
+            }
+            Token::TerminatingLoop(terminating_loop) => {
+                if terminating_loop.data_type != DataType::INTEGER {
+                    self.errors.push(TypeError::MismatchedTypes {
+                        expected: DataType::INTEGER.to_type_name(),
+                        found: terminating_loop.data_type.to_type_name(),
+                        span: terminating_loop.type_span,
+                    });
+                }
+
+                self.expect_type(variable_history, &terminating_loop.from, &DataType::INTEGER);
+                self.expect_type(variable_history, &terminating_loop.to, &DataType::INTEGER);
+                self.expect_type(variable_history, &terminating_loop.step, &DataType::INTEGER);
+                self.check_block(parent, &terminating_loop.functionality);
+            }
+            Token::Function(_) | Token::Break(_) | Token::Continue(_) => {}
+        }}
+    }
+
+    /// Checks `assignment` and reports it if it isn't a `data_type`
+    fn expect_type(&mut self, variable_history: &VariableHistory, assignment: &Assignment, data_type: &DataType) {
+        let Some(found) = self.check_assignment(variable_history, assignment) else { return };
+
+        if found != *data_type {
+            self.errors.push(TypeError::MismatchedTypes {
+                expected: data_type.to_type_name(),
+                found: found.to_type_name(),
+                span: assignment.span(),
+            });
+        }
+    }
+
+    /// Returns the type `assignment` evaluates to after checking every term in it
+    ///
+    /// Returns None if an error was found (and reported), so one mistake isn't reported again by
+    /// everything that uses it
+    fn check_assignment(&mut self, variable_history: &VariableHistory, assignment: &Assignment) -> Option<DataType> { match assignment {
+        Assignment::EVAL(first_term, operator, second_term, span) => {
+            let first_type  = self.check_assignment(variable_history, first_term)?;
+            let second_type = self.check_assignment(variable_history, second_term)?;
+
+            let is_valid = match operator {
+                MathOperator::ADD | MathOperator::SUB | MathOperator::MUL | MathOperator::DIV => {
+                    is_number(&first_type) && is_number(&second_type)
+                }
+                _ => {
+                    first_type.is_integer() && second_type.is_integer()
+                }
+            };
+            if !is_valid {
+                return self.invalid_operands(operator.to_symbol(), &first_type, &second_type, *span)
+            }
+
+            return Some(assignment.evaluate_type(variable_history))
+        }
+        Assignment::CMP(first_term, operator, second_term, span) => {
+            let first_type  = self.check_assignment(variable_history, first_term)?;
+            let second_type = self.check_assignment(variable_history, second_term)?;
+
+            let is_valid = match operator {
+                ComparisonOperator::EQ | ComparisonOperator::NEQ => {
+                    (is_number(&first_type) && is_number(&second_type)) || (first_type == DataType::BOOL && second_type == DataType::BOOL)
+                }
+                _ => {
+                    is_number(&first_type) && is_number(&second_type)
+                }
+            };
+            if !is_valid {
+                return self.invalid_operands(operator.to_symbol(), &first_type, &second_type, *span)
+            }
+
+            return Some(DataType::BOOL)
+        }
+        Assignment::LOGIC(first_term, operator, second_term, span) => {
+            let first_type  = self.check_assignment(variable_history, first_term)?;
+            let second_type = self.check_assignment(variable_history, second_term)?;
+
+            if first_type != DataType::BOOL || second_type != DataType::BOOL {
+                return self.invalid_operands(operator.to_symbol(), &first_type, &second_type, *span)
+            }
+
+            return Some(DataType::BOOL)
+        }
+        Assignment::UNARY(operator, term, span) => {
+            let term_type = self.check_assignment(variable_history, term)?;
+
+            let is_valid = match operator {
+                UnaryOperator::NEG  => { is_number(&term_type)        }
+                UnaryOperator::BNOT => { term_type.is_integer()       }
+                UnaryOperator::NOT  => { term_type == DataType::BOOL  }
+            };
+            if !is_valid {
+                self.errors.push(TypeError::InvalidOperand { operator: operator.to_symbol(), found: term_type.to_type_name(), span: *span });
+                return None
+            }
+
+            return Some(assignment.evaluate_type(variable_history))
+        }
+        Assignment::CAST(term, data_type, span) => {
+            let term_type = self.check_assignment(variable_history, term)?;
+
+            let is_valid = match (&term_type, data_type) {
+                (DataType::INTEGER, DataType::FLOAT)   => { true  }
+                (DataType::FLOAT,   DataType::INTEGER) => { true  }
+                (DataType::BOOL,    DataType::INTEGER) => { true  }
+                (from, to)                             => { from == to }
+            };
+            if !is_valid {
+                self.errors.push(TypeError::InvalidConversion { from: term_type.to_type_name(), to: data_type.to_type_name(), span: *span });
+                return None
+            }
+
+            return Some(data_type.clone())
+        }
+        Assignment::FUNC(name, return_type, arguments, span) => {
+            let Some(function) = self.function_history.find_by_name(name) else {
+                return Some(return_type.clone())
+            };
+
+            // Only compare the arguments to the subroutine's once there's the right amount of them
+            if arguments.len() != function.arguments.len() {
+                for argument in arguments.iter() {
+                    self.check_assignment(variable_history, argument);
+                }
+
+                self.errors.push(TypeError::WrongArgumentCount {
+                    name: name.clone(),
+                    expected: function.arguments.len(),
+                    found: arguments.len(),
+                    span: *span,
+                    definition_span: function.span,
+                });
+                return None
+            }
+
+            for (argument, parameter) in arguments.iter().zip(function.arguments.iter()) {
+                self.expect_type(variable_history, argument, &parameter.data_type);
+            }
+
+            return Some(return_type.clone())
+        }
+        _ => {
+            return Some(assignment.evaluate_type(variable_history))
+        }
+    }}
+
+    /// Reports that `operator` can't be used between a `first_type` and a `second_type`
+    fn invalid_operands(&mut self, operator: String, first_type: &DataType, second_type: &DataType, span: Span) -> Option<DataType> {
+        self.errors.push(TypeError::InvalidOperands {
+            operator,
+            first: first_type.to_type_name(),
+            second: second_type.to_type_name(),
+            span,
+        });
+
+        return None
+    }
+}
+
+
+/// Whether values of `data_type` can be used in arithmetic
+fn is_number(data_type: &DataType) -> bool {
+    return data_type.is_integer() || data_type.is_float()
+}
+
+/// Whether every path through `block` ends in an `expose`
+///
+/// A `cmpr` only always returns if it has an `otherwise` branch and every branch always returns.
+/// A `repeat` or `loop` never does, as its body may never run
+fn always_returns(block: &[Token]) -> bool {
+    return block.iter().any(|token| match token {
+        Token::Return(_) => { true }
+        Token::ConditionalStatement(conditional_statement) => {
+            conditional_statement.condition_fields.iter().any(|(condition, _)| condition.is_none())
+                && conditional_statement.condition_fields.iter().all(|(_, functionality)| always_returns(functionality))
+        }
+        _ => { false }
+    })
+}